@@ -1,4 +1,5 @@
-use super::{AppResult, Error, Logger};
+use super::{AppResult, Error, Logger, OAuth};
+use crate::core::signatures_match;
 
 use axum::http::HeaderMap;
 use chrono::Local;
@@ -19,6 +20,9 @@ pub struct Auth {
     // Client info
     /// A slack-app signing-secret.
     signing_secret: String,
+
+    /// The OAuth configuration, for apps installed in multiple workspaces.
+    oauth: Option<OAuth>,
 }
 
 impl Logger for Auth {
@@ -39,9 +43,24 @@ impl Auth {
             bot_token,
             user_token,
             signing_secret,
+            oauth: None,
         }
     }
 
+    /// Configures the OAuth installation flow, resolving the tokens of each request
+    /// from the installation of the workspace it originates from.
+    #[must_use]
+    pub fn oauth(mut self, oauth: OAuth) -> Self {
+        self.oauth = Some(oauth);
+        self
+    }
+
+    /// Returns the OAuth configuration, if any.
+    #[must_use]
+    pub const fn get_oauth(&self) -> Option<&OAuth> {
+        self.oauth.as_ref()
+    }
+
     /// Returns the bot-token, if any.
     #[must_use]
     pub fn bot_token(&self) -> Option<String> {
@@ -68,6 +87,16 @@ impl Auth {
     ///
     pub fn run_pre_startup_checks(&self) {
         assert!(!self.signing_secret.is_empty(), "Signing secret is empty! If you instantiated your Auth using `Auth::default()` you need to use `Auth::new(signing_secret, Option<bot_token>, Option<user_token>)` instead.");
+        self.run_token_checks();
+    }
+
+    /// Alerts the user of non-registered tokens, which could lead to decreased functionality.
+    pub fn run_token_checks(&self) {
+        // Tokens are resolved from the installations
+        if self.oauth.is_some() {
+            return;
+        }
+
         if self.bot_token.is_none() {
             Self::warn("No Bot-Token supplied. Some features won't be available or be limited.");
         }
@@ -76,21 +105,23 @@ impl Auth {
         }
     }
 
-    /// Authenticates a slack-payload.
+    /// Verifies the signature of a request-body from slack.
     ///
     /// # Errors
     ///
-    /// Errors will occur if the slack-payload is unverifiable, unparseable or alike.
+    /// Errors will occur if the headers are missing, the timestamp is too old or the signatures doesn't match.
     ///
-    pub fn sanitize_payload(&self, payload: &String, headers: &HeaderMap) -> AppResult<String> {
-        Self::log("Sanitizing new payload.");
-
+    pub fn verify(&self, payload: &str, headers: &HeaderMap) -> AppResult<()> {
         // Get headers
-        let Some(slack_ts_header) = headers.get("X-Slack-Request-Timestamp") else { 
-            return Err(Error::Authentication("Missing timestamp header!".to_string())) 
+        let Some(slack_ts_header) = headers.get("X-Slack-Request-Timestamp") else {
+            return Err(Error::Authentication(
+                "Missing timestamp header!".to_string(),
+            ));
         };
-        let Some(slack_signature_header) = headers.get("X-Slack-Signature") else { 
-            return Err(Error::Authentication("Missing signature header!".to_string())) 
+        let Some(slack_signature_header) = headers.get("X-Slack-Signature") else {
+            return Err(Error::Authentication(
+                "Missing signature header!".to_string(),
+            ));
         };
 
         // Parse timestamp to integer
@@ -136,10 +167,27 @@ impl Auth {
         };
 
         // Ensure signature is matching hmac
-        if format!("{SLACK_ENCRYPTION_VERSION}={}", hex::encode(hmac)) != signature {
+        if !signatures_match(
+            &signature,
+            &format!("{SLACK_ENCRYPTION_VERSION}={}", hex::encode(hmac)),
+        ) {
             return Err(Error::Authentication("Signatures didn't match".to_string()));
         };
 
+        Ok(())
+    }
+
+    /// Authenticates a slack-payload.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the slack-payload is unverifiable, unparseable or alike.
+    ///
+    pub fn sanitize_payload(&self, payload: &str, headers: &HeaderMap) -> AppResult<String> {
+        Self::log("Sanitizing new payload.");
+
+        self.verify(payload, headers)?;
+
         Self::log("OK");
 
         // Decode payload
@@ -150,4 +198,20 @@ impl Auth {
             ))),
         }
     }
+
+    /// Authenticates a slack-payload with a JSON-body, such as the ones sent from the Events API.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the slack-payload is unverifiable.
+    ///
+    pub fn sanitize_json_payload(&self, payload: &str, headers: &HeaderMap) -> AppResult<String> {
+        Self::log("Sanitizing new JSON-payload.");
+
+        self.verify(payload, headers)?;
+
+        Self::log("OK");
+
+        Ok(payload.to_string())
+    }
 }
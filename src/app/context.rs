@@ -0,0 +1,174 @@
+use super::{AppResult, Auth, Error, Incoming, InstallationStore, ResponseMessage};
+use crate::pre::WebClient;
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Arc,
+};
+
+/// The context of an incoming request, passed to handlers along with the payload.
+///
+/// The context gives access to the shared state of the app, the tokens from the apps [`Auth`],
+/// and a way to respond to the interaction through its `response_url`.
+///
+/// Middleware can enrich the context with extensions, which are available to the handler of the request.
+pub struct Context<S = ()> {
+    state: Arc<S>,
+    bot_token: Option<String>,
+    user_token: Option<String>,
+    signing_secret: Arc<str>,
+    response_url: Option<String>,
+    extensions: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+    installations: Option<Arc<dyn InstallationStore>>,
+    client: WebClient,
+}
+
+impl<S> Clone for Context<S> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            bot_token: self.bot_token.clone(),
+            user_token: self.user_token.clone(),
+            signing_secret: self.signing_secret.clone(),
+            response_url: self.response_url.clone(),
+            extensions: self.extensions.clone(),
+            installations: self.installations.clone(),
+            client: self.client.clone(),
+        }
+    }
+}
+
+impl<S> Context<S> {
+    /// Creates a new context from the shared state, authentication and Web API client of an app.
+    pub(super) fn new(state: Arc<S>, auth: &Auth, client: WebClient) -> Self {
+        Self {
+            state,
+            bot_token: auth.bot_token(),
+            user_token: auth.user_token(),
+            signing_secret: auth.signing_secret().into(),
+            response_url: None,
+            extensions: HashMap::new(),
+            installations: auth.get_oauth().map(super::OAuth::store),
+            client,
+        }
+    }
+
+    /// Resolves the tokens of the context from the installation of the workspace the request originates from,
+    /// if the app uses the OAuth installation flow.
+    ///
+    /// Organization-wide installations are used when the workspace has no installation of its own.
+    pub(super) async fn authorize(mut self, incoming: &Incoming) -> AppResult<Self> {
+        let Some(installations) = self.installations.clone() else {
+            return Ok(self);
+        };
+
+        let enterprise_id = incoming.enterprise_id();
+        let team_id = incoming.team_id();
+
+        let mut installation = installations.find(enterprise_id, team_id).await?;
+        if installation.is_none() && enterprise_id.is_some() {
+            installation = installations.find(enterprise_id, None).await?;
+        }
+
+        match installation {
+            Some(installation) => {
+                self.bot_token = installation.bot_token;
+                self.user_token = installation.user_token;
+                Ok(self)
+            }
+            None => Err(Error::Authentication(format!(
+                "No installation found for team {team_id:?} (enterprise: {enterprise_id:?})"
+            ))),
+        }
+    }
+
+    /// Binds the context to the `response_url` of an interaction.
+    pub(super) fn with_response_url(mut self, response_url: Option<String>) -> Self {
+        self.response_url = response_url;
+        self
+    }
+
+    /// Returns the shared state of the app.
+    #[must_use]
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Returns the token used for requests, preferring the bot-token over the user-token.
+    #[must_use]
+    pub fn token(&self) -> Option<&str> {
+        self.bot_token().or_else(|| self.user_token())
+    }
+
+    /// Returns the bot-token, if any.
+    #[must_use]
+    pub fn bot_token(&self) -> Option<&str> {
+        self.bot_token.as_deref()
+    }
+
+    /// Returns the user-token, if any.
+    #[must_use]
+    pub fn user_token(&self) -> Option<&str> {
+        self.user_token.as_deref()
+    }
+
+    /// Returns the signing-secret of the app, e.g. for signing the private-metadata of views.
+    #[must_use]
+    pub fn signing_secret(&self) -> &str {
+        &self.signing_secret
+    }
+
+    /// Returns the `response_url` of the interaction, if any.
+    #[must_use]
+    pub fn response_url(&self) -> Option<&str> {
+        self.response_url.as_deref()
+    }
+
+    /// Inserts an extension into the context, replacing any previous extension of the same type.
+    pub fn insert<T: Any + Send + Sync>(&mut self, extension: T) {
+        self.extensions
+            .insert(TypeId::of::<T>(), Arc::new(extension));
+    }
+
+    /// Returns the extension of the type, if any.
+    #[must_use]
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.extensions
+            .get(&TypeId::of::<T>())
+            .and_then(|extension| extension.downcast_ref())
+    }
+
+    /// Returns the [`WebClient`] of the app, using the token of the context.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the app has no tokens.
+    pub fn client(&self) -> AppResult<WebClient> {
+        match self.token() {
+            Some(token) => Ok(self.web_client(token)),
+            None => Err(Error::Authentication(
+                "No token available for the Web API client.".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the [`WebClient`] of the app, using another token, e.g. an app-level token.
+    pub(super) fn web_client(&self, token: &str) -> WebClient {
+        self.client.clone().with_token(token)
+    }
+
+    /// Sends a response to the `response_url` of the interaction.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the interaction has no `response_url`, or if the response is rejected.
+    pub async fn respond(&self, message: &ResponseMessage) -> AppResult<()> {
+        let Some(response_url) = self.response_url() else {
+            return Err(Error::Parsing(
+                "Tried to respond to an interaction without a response_url.".to_string(),
+            ));
+        };
+
+        Ok(message.send(response_url).await?)
+    }
+}
@@ -4,22 +4,44 @@ use colored::Colorize;
 use std::fmt::Display;
 
 /// A bolt-rs App-related error
+#[derive(Debug)]
 pub enum Error {
+    /// Errors from bolt-rs, e.g. from requests to the slack Web API made by handlers.
+    Bolt(crate::Error),
+
     /// Errors regarding parsing of requests, interactions or alike.
     Parsing(String),
 
     /// Problems regarding Authentification of incoming requests.
     Authentication(String),
 
+    /// Errors relating to persisting or finding installations.
+    Installation(String),
+
+    /// Errors relating to the OAuth installation flow.
+    OAuth(String),
+
+    /// Errors relating to events from the slack Events API.
+    Event(String),
+
     /// Errors relating to `block-actions` interactions from slack.
     BlockAction(String),
 
+    /// Errors relating to `block-suggestion` interactions from slack.
+    BlockSuggestion(String),
+
     /// Errors relating to `message-actions` interactions from slack.
     MessageAction(String),
 
     /// Errors relating to `shortcuts` interactions from slack.
     Shortcut(String),
 
+    /// Errors relating to Socket Mode connections.
+    SocketMode(String),
+
+    /// Errors relating to slash-commands from slack.
+    SlashCommand(String),
+
     /// Errors relating to `view-closed` interactions from slack.
     ViewClosed(String),
 
@@ -39,6 +61,10 @@ impl IntoResponse for Error {
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Bolt(error) => {
+                let banner = "[ERROR][Bolt]".red();
+                write!(f, "{banner} {error}")
+            }
             Self::Parsing(error) => {
                 let banner = "[ERROR][Parsing]".red();
                 write!(f, "{banner} {error}")
@@ -47,10 +73,26 @@ impl Display for Error {
                 let banner = "[ERROR][Authentication]".red();
                 write!(f, "{banner} {error}")
             }
+            Self::Installation(error) => {
+                let banner = "[ERROR][Installation]".red();
+                write!(f, "{banner} {error}")
+            }
+            Self::OAuth(error) => {
+                let banner = "[ERROR][OAuth]".red();
+                write!(f, "{banner} {error}")
+            }
+            Self::Event(error) => {
+                let banner = "[ERROR][Event]".red();
+                write!(f, "{banner} {error}")
+            }
             Self::BlockAction(error) => {
                 let banner = "[ERROR][BlockAction]".red();
                 write!(f, "{banner} {error}")
             }
+            Self::BlockSuggestion(error) => {
+                let banner = "[ERROR][BlockSuggestion]".red();
+                write!(f, "{banner} {error}")
+            }
             Self::MessageAction(error) => {
                 let banner = "[ERROR][MessageAction]".red();
                 write!(f, "{banner} {error}")
@@ -59,6 +101,14 @@ impl Display for Error {
                 let banner = "[ERROR][Shortcut]".red();
                 write!(f, "{banner} {error}")
             }
+            Self::SocketMode(error) => {
+                let banner = "[ERROR][SocketMode]".red();
+                write!(f, "{banner} {error}")
+            }
+            Self::SlashCommand(error) => {
+                let banner = "[ERROR][SlashCommand]".red();
+                write!(f, "{banner} {error}")
+            }
             Self::ViewClosed(error) => {
                 let banner = "[ERROR][ViewClosed]".red();
                 write!(f, "{banner} {error}")
@@ -71,8 +121,20 @@ impl Display for Error {
     }
 }
 
+impl From<crate::Error> for Error {
+    fn from(value: crate::Error) -> Self {
+        Self::Bolt(value)
+    }
+}
+
 impl From<json::Error> for Error {
     fn from(value: json::Error) -> Self {
         Self::Parsing(value.to_string())
     }
 }
+
+impl From<serde_urlencoded::de::Error> for Error {
+    fn from(value: serde_urlencoded::de::Error) -> Self {
+        Self::Parsing(value.to_string())
+    }
+}
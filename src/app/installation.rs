@@ -0,0 +1,187 @@
+use super::{json, AppResult, Error};
+use crate::pre::{skip_serializing_none, Deserialize, Serialize};
+use futures::future::BoxFuture;
+use std::{collections::HashMap, io::ErrorKind, path::PathBuf, sync::RwLock};
+
+/// An installation of the app in a workspace, or across an enterprise organization.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Installation {
+    /// The id of the app.
+    pub app_id: Option<String>,
+
+    /// The enterprise the app is installed in, if any.
+    pub enterprise_id: Option<String>,
+
+    /// The team (Workspace) the app is installed in.
+    /// Empty for organization-wide installations.
+    pub team_id: Option<String>,
+
+    /// The bot-token of the installation.
+    pub bot_token: Option<String>,
+
+    /// The user-id of the bot.
+    pub bot_user_id: Option<String>,
+
+    /// The scopes granted to the bot, comma-separated.
+    pub bot_scopes: Option<String>,
+
+    /// The id of the user who installed the app.
+    pub user_id: Option<String>,
+
+    /// The user-token of the installing user, if any user-scopes were requested.
+    pub user_token: Option<String>,
+
+    /// The scopes granted to the user-token, comma-separated.
+    pub user_scopes: Option<String>,
+
+    /// If the app was installed across an enterprise organization.
+    #[serde(default)]
+    pub is_enterprise_install: bool,
+}
+
+impl Installation {
+    /// Returns the key of the installation, as used by the installation-stores.
+    fn key(&self) -> String {
+        if self.is_enterprise_install {
+            key(self.enterprise_id.as_deref(), None)
+        } else {
+            key(self.enterprise_id.as_deref(), self.team_id.as_deref())
+        }
+    }
+}
+
+/// Creates a key for an enterprise and team.
+fn key(enterprise_id: Option<&str>, team_id: Option<&str>) -> String {
+    format!(
+        "{}-{}",
+        enterprise_id.unwrap_or("none"),
+        team_id.unwrap_or("none")
+    )
+}
+
+/// Persists the installations of an app, used for resolving the tokens of incoming requests.
+///
+/// Implementations find installations by exact match, where a missing team-id
+/// refers to an organization-wide installation.
+pub trait InstallationStore: Send + Sync {
+    /// Saves an installation, replacing any previous installation for the same workspace or organization.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the installation can't be persisted.
+    fn save(&self, installation: Installation) -> BoxFuture<'_, AppResult<()>>;
+
+    /// Finds the installation for an enterprise and team, if any.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the installations can't be read.
+    fn find<'a>(
+        &'a self,
+        enterprise_id: Option<&'a str>,
+        team_id: Option<&'a str>,
+    ) -> BoxFuture<'a, AppResult<Option<Installation>>>;
+}
+
+/// An [`InstallationStore`] keeping installations in memory, which are lost when the app stops.
+#[derive(Debug, Default)]
+pub struct MemoryInstallationStore {
+    installations: RwLock<HashMap<String, Installation>>,
+}
+
+impl MemoryInstallationStore {
+    /// Creates a new, empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl InstallationStore for MemoryInstallationStore {
+    fn save(&self, installation: Installation) -> BoxFuture<'_, AppResult<()>> {
+        Box::pin(async move {
+            match self.installations.write() {
+                Ok(mut installations) => {
+                    installations.insert(installation.key(), installation);
+                    Ok(())
+                }
+                Err(error) => Err(Error::Installation(format!(
+                    "Couldn't save installation: {error}"
+                ))),
+            }
+        })
+    }
+
+    fn find<'a>(
+        &'a self,
+        enterprise_id: Option<&'a str>,
+        team_id: Option<&'a str>,
+    ) -> BoxFuture<'a, AppResult<Option<Installation>>> {
+        Box::pin(async move {
+            match self.installations.read() {
+                Ok(installations) => Ok(installations.get(&key(enterprise_id, team_id)).cloned()),
+                Err(error) => Err(Error::Installation(format!(
+                    "Couldn't find installation: {error}"
+                ))),
+            }
+        })
+    }
+}
+
+/// An [`InstallationStore`] keeping each installation as a JSON-file within a directory.
+#[derive(Debug, Clone)]
+pub struct FileInstallationStore {
+    directory: PathBuf,
+}
+
+impl FileInstallationStore {
+    /// Creates a new store in the directory, which is created when the first installation is saved.
+    #[must_use]
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Returns the path of the file for a key.
+    fn path(&self, key: &str) -> PathBuf {
+        self.directory.join(format!("{key}.json"))
+    }
+}
+
+impl InstallationStore for FileInstallationStore {
+    fn save(&self, installation: Installation) -> BoxFuture<'_, AppResult<()>> {
+        Box::pin(async move {
+            let contents = json::to_string_pretty(&installation)?;
+
+            tokio::fs::create_dir_all(&self.directory)
+                .await
+                .map_err(|error| {
+                    Error::Installation(format!("Couldn't create directory: {error}"))
+                })?;
+
+            tokio::fs::write(self.path(&installation.key()), contents)
+                .await
+                .map_err(|error| {
+                    Error::Installation(format!("Couldn't save installation: {error}"))
+                })
+        })
+    }
+
+    fn find<'a>(
+        &'a self,
+        enterprise_id: Option<&'a str>,
+        team_id: Option<&'a str>,
+    ) -> BoxFuture<'a, AppResult<Option<Installation>>> {
+        Box::pin(async move {
+            match tokio::fs::read_to_string(self.path(&key(enterprise_id, team_id))).await {
+                Ok(contents) => Ok(Some(json::from_str(&contents)?)),
+                Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+                Err(error) => Err(Error::Installation(format!(
+                    "Couldn't read installation: {error}"
+                ))),
+            }
+        })
+    }
+}
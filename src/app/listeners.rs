@@ -0,0 +1,309 @@
+use super::{
+    json, Action, App, AppResult, BlockAction, BlockSuggestion, Context, Error, EventCallback,
+    Incoming, Interaction, Logger, MessageAction, Middleware, Next, RequestKind, ResponseMessage,
+    Shortcut, SlashCommand, Suggestions, ViewClosed, ViewSubmission, ViewSubmissionResponse,
+};
+use futures::{future::BoxFuture, FutureExt};
+use std::{any::Any, collections::HashMap, panic::AssertUnwindSafe, sync::Arc};
+use tokio::sync::Semaphore;
+
+/// A collection of closures
+pub(super) type Interactions<T, S, R = ()> =
+    HashMap<String, Box<dyn Fn(T, Context<S>) -> BoxFuture<'static, AppResult<R>> + Send + Sync>>;
+
+/// A collection of block-action closures, keyed by action-id.
+///
+/// Each closure can optionally be constrained to a specific block-id.
+pub(super) type Actions<S> = HashMap<
+    String,
+    Vec<(
+        Option<String>,
+        Box<
+            dyn Fn(BlockAction, Action, Context<S>) -> BoxFuture<'static, AppResult<()>>
+                + Send
+                + Sync,
+        >,
+    )>,
+>;
+
+/// Every handler and middleware registered on an [`App`].
+///
+/// The listeners are shared between the transports of the app (HTTP and Socket Mode),
+/// which only differ in how payloads are received and replied to.
+pub(super) struct Listeners<S> {
+    pub(super) block_actions: Actions<S>,
+    pub(super) block_suggestions: Interactions<BlockSuggestion, S, Suggestions>,
+    pub(super) commands: Interactions<SlashCommand, S, Option<ResponseMessage>>,
+    pub(super) events: Interactions<EventCallback, S>,
+    pub(super) message_actions: Interactions<MessageAction, S>,
+    pub(super) shortcuts: Interactions<Shortcut, S>,
+    pub(super) view_closes: Interactions<ViewClosed, S>,
+    pub(super) view_submissions: Interactions<ViewSubmission, S, Option<ViewSubmissionResponse>>,
+    pub(super) middleware: Vec<Middleware<S>>,
+
+    /// Bounds the handlers running after their request has been acknowledged,
+    /// if the listeners are lazy.
+    pub(super) lazy: Option<Arc<Semaphore>>,
+}
+
+impl<S> Default for Listeners<S> {
+    fn default() -> Self {
+        Self {
+            block_actions: HashMap::new(),
+            block_suggestions: HashMap::new(),
+            commands: HashMap::new(),
+            events: HashMap::new(),
+            message_actions: HashMap::new(),
+            shortcuts: HashMap::new(),
+            view_closes: HashMap::new(),
+            view_submissions: HashMap::new(),
+            middleware: Vec::new(),
+            lazy: None,
+        }
+    }
+}
+
+impl<S: Send + Sync + 'static> Listeners<S> {
+    /// Runs a request through the middleware to its handler, returning the body to reply with, if any.
+    ///
+    /// Lazy listeners acknowledge the request immediately, and run the handler on a separate task,
+    /// reporting errors and panics through the logger. Option-requests are always handled directly,
+    /// as the options has to be sent in the reply.
+    pub(super) async fn run(
+        self: Arc<Self>,
+        incoming: Incoming,
+        context: Context<S>,
+    ) -> AppResult<Option<json::Value>> {
+        let context = context.authorize(&incoming).await?;
+
+        let Some(semaphore) = self.lazy.clone().filter(|_| !incoming.needs_reply()) else {
+            return Next::new(self).run(incoming, context).await;
+        };
+
+        tokio::spawn(async move {
+            // The semaphore is never closed
+            let Ok(_permit) = semaphore.acquire_owned().await else {
+                return;
+            };
+
+            let r#type = incoming.r#type().unwrap_or_default().to_string();
+
+            match AssertUnwindSafe(Next::new(self).run(incoming, context))
+                .catch_unwind()
+                .await
+            {
+                Ok(Ok(_)) => (),
+                Ok(Err(error)) => <App>::error(&format!("Lazy '{type}' handler failed: {error}")),
+                Err(panic) => <App>::error(&format!(
+                    "Lazy '{type}' handler panicked: {}",
+                    Self::panic_message(&*panic)
+                )),
+            }
+        });
+
+        Ok(None)
+    }
+
+    /// Gets the message of a captured panic.
+    fn panic_message(panic: &(dyn Any + Send)) -> &str {
+        panic
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Unknown panic")
+    }
+
+    /// Dispatches a request that has passed through the middleware to its handler,
+    /// returning the body to reply with, if any.
+    pub(super) async fn route(
+        &self,
+        incoming: Incoming,
+        context: Context<S>,
+    ) -> AppResult<Option<json::Value>> {
+        match incoming.kind {
+            RequestKind::Interaction => self.interaction(incoming.payload, context).await,
+            RequestKind::Event => self.event(incoming.payload, context).await.map(|()| None),
+            RequestKind::Command => {
+                self.command(json::from_value(incoming.payload)?, context)
+                    .await
+            }
+        }
+    }
+
+    /// Handles an interaction, returning the body to reply with, if any.
+    async fn interaction(
+        &self,
+        payload: json::Value,
+        context: Context<S>,
+    ) -> AppResult<Option<json::Value>> {
+        // Get type
+        let Some(r#type) = payload["type"].as_str().map(ToString::to_string) else {
+            return Err(Error::Parsing(
+                "Received new payload without a type!".to_string(),
+            ));
+        };
+
+        <App>::log(&format!("Recieved a new '{type}' interaction"));
+
+        // Match type of interaction to handle
+        match r#type.as_str() {
+            "block_actions" => self.block_actions(payload, context).await?,
+            "block_suggestion" => {
+                let suggestions = Self::handle(&self.block_suggestions, payload, context).await?;
+                return Ok(Some(json::to_value(suggestions)?));
+            }
+            "message_action" => Self::handle(&self.message_actions, payload, context).await?,
+            "shortcut" => Self::handle(&self.shortcuts, payload, context).await?,
+            "view_closed" => Self::handle_unreplied(&self.view_closes, payload, context).await?,
+            "view_submission" => {
+                if let Some(response) =
+                    Self::handle(&self.view_submissions, payload, context).await?
+                {
+                    return Ok(Some(json::to_value(response)?));
+                }
+            }
+
+            t => {
+                return Err(Error::Parsing(format!(
+                    "'{t}' is not a known interaction type!"
+                )))
+            }
+        };
+
+        Ok(None)
+    }
+
+    /// Handles an `event_callback` from the Events API.
+    async fn event(&self, payload: json::Value, context: Context<S>) -> AppResult<()> {
+        Self::handle_unreplied(&self.events, payload, context).await
+    }
+
+    /// Handles a slash-command, returning the body to reply with, if any.
+    async fn command(
+        &self,
+        command: SlashCommand,
+        context: Context<S>,
+    ) -> AppResult<Option<json::Value>> {
+        <App>::log(&format!("Recieved a new '{}' command", command.command));
+
+        match Self::dispatch(&self.commands, command, context).await? {
+            Some(message) => Ok(Some(json::to_value(message)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Gets the type of an incoming payload.
+    pub(super) fn get_type(payload: &str) -> AppResult<String> {
+        // Parse json
+        let json: json::Map<String, json::Value> = json::from_str(payload)?;
+
+        // Get type
+        match json.get("type") {
+            Some(t) => Ok(t.to_string().replace('"', "")),
+            None => Err(Error::Parsing(
+                "Received new payload without a type!".to_string(),
+            )),
+        }
+    }
+
+    /// Parses an interaction.
+    fn parse<T: Interaction>(interaction: json::Value) -> AppResult<T> {
+        json::from_value::<T>(interaction)
+            .map_err(|error| T::error(format!("Tried to parse JSON to struct: {error}")))
+    }
+
+    /// Parses an interaction and runs the closure for it.
+    async fn handle<T: Interaction + Send, R>(
+        closures: &Interactions<T, S, R>,
+        interaction: json::Value,
+        context: Context<S>,
+    ) -> AppResult<R> {
+        Self::dispatch(closures, Self::parse(interaction)?, context).await
+    }
+
+    /// Parses an interaction slack doesn't expect a reply to, and runs the closure for it.
+    ///
+    /// Interactions without a closure are acknowledged with a warning, as slack retries failed
+    /// deliveries, eventually disabling the event-subscriptions of the app.
+    async fn handle_unreplied<T: Interaction + Send>(
+        closures: &Interactions<T, S>,
+        interaction: json::Value,
+        context: Context<S>,
+    ) -> AppResult<()> {
+        let interaction = Self::parse::<T>(interaction)?;
+
+        if let Some(identifier) = interaction
+            .identifier()
+            .filter(|identifier| !closures.contains_key(identifier))
+        {
+            <App>::warn(&format!(
+                "Unhandled {}: '{identifier}'",
+                T::identifier_name()
+            ));
+            return Ok(());
+        }
+
+        Self::dispatch(closures, interaction, context).await
+    }
+
+    /// Finds and runs the closure for an already parsed interaction.
+    async fn dispatch<T: Interaction + Send, R>(
+        closures: &Interactions<T, S, R>,
+        interaction: T,
+        context: Context<S>,
+    ) -> AppResult<R> {
+        // Get identifier
+        let Some(identifier) = interaction.identifier() else {
+            return Err(T::error(format!("Missing {}", T::identifier_name())));
+        };
+
+        // Get and run closure
+        match closures.get(&identifier) {
+            Some(closure) => {
+                let context = context.with_response_url(interaction.response_url());
+                closure(interaction, context).await
+            }
+            None => Err(T::error(format!(
+                "Unknown {}: '{}'",
+                T::identifier_name(),
+                identifier
+            ))),
+        }
+    }
+
+    /// Handles incoming block-actions.
+    ///
+    /// Each action in the payload is dispatched to the closure registered for its action-id.
+    /// Closures constrained to the block-id of the action takes precedence,
+    /// and actions without a closure are acknowledged with a warning.
+    async fn block_actions(&self, interaction: json::Value, context: Context<S>) -> AppResult<()> {
+        let interaction = Self::parse::<BlockAction>(interaction)?;
+
+        let context = context.with_response_url(interaction.response_url());
+
+        for action in interaction.actions.clone() {
+            let candidates = self
+                .block_actions
+                .get(&action.action_id)
+                .map_or(&[][..], Vec::as_slice);
+
+            // Find closure, preferring the ones constrained to the block-id
+            let closure = candidates
+                .iter()
+                .find(|(block_id, _)| block_id.is_some() && *block_id == action.block_id)
+                .or_else(|| candidates.iter().find(|(block_id, _)| block_id.is_none()));
+
+            match closure {
+                Some((_, closure)) => closure(interaction.clone(), action, context.clone()).await?,
+                None => <App>::warn(&format!(
+                    "Unhandled {}: '{}' (block_id: {:?})",
+                    BlockAction::identifier_name(),
+                    action.action_id,
+                    action.block_id
+                )),
+            }
+        }
+
+        Ok(())
+    }
+}
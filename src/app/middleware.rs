@@ -0,0 +1,198 @@
+use super::{json, AppResult, Context, Listeners};
+use futures::future::BoxFuture;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+
+/// A middleware closure, optionally constrained to a listener-identifier.
+pub(super) type Middleware<S> = (
+    Option<String>,
+    Box<
+        dyn Fn(Incoming, Context<S>, Next<S>) -> BoxFuture<'static, AppResult<Option<json::Value>>>
+            + Send
+            + Sync,
+    >,
+);
+
+/// The kind of an [`Incoming`] request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestKind {
+    /// An interaction, such as a shortcut, block-action or view-submission.
+    Interaction,
+
+    /// An `event_callback` from the Events API.
+    Event,
+
+    /// A slash-command.
+    Command,
+}
+
+/// A parsed request, passed through the middleware of an app before reaching a handler.
+///
+/// The payload is kept as JSON, so middleware can inspect requests of any kind.
+/// Use [`Incoming::parse`] to get the typed payload.
+#[derive(Debug, Clone)]
+pub struct Incoming {
+    /// The kind of the request.
+    pub kind: RequestKind,
+
+    /// The JSON-payload of the request.
+    pub payload: json::Value,
+}
+
+impl Incoming {
+    /// Creates a new request.
+    #[must_use]
+    pub const fn new(kind: RequestKind, payload: json::Value) -> Self {
+        Self { kind, payload }
+    }
+
+    /// Parses the payload into a typed payload, e.g. a [`Shortcut`](super::Shortcut).
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the payload doesn't match the type.
+    pub fn parse<T: DeserializeOwned>(&self) -> AppResult<T> {
+        Ok(json::from_value(self.payload.clone())?)
+    }
+
+    /// Returns the type of the request, e.g. `shortcut` for interactions,
+    /// `app_mention` for events or `/deploy` for slash-commands.
+    #[must_use]
+    pub fn r#type(&self) -> Option<&str> {
+        match self.kind {
+            RequestKind::Interaction => self.payload["type"].as_str(),
+            RequestKind::Event => self.payload["event"]["type"].as_str(),
+            RequestKind::Command => self.payload["command"].as_str(),
+        }
+    }
+
+    /// Returns the id of the user who caused the request, if any.
+    #[must_use]
+    pub fn user_id(&self) -> Option<&str> {
+        match self.kind {
+            RequestKind::Interaction => self.payload["user"]["id"].as_str(),
+            RequestKind::Event => self.payload["event"]["user"].as_str(),
+            RequestKind::Command => self.payload["user_id"].as_str(),
+        }
+    }
+
+    /// Returns the id of the team (Workspace) the request originates from, if any.
+    #[must_use]
+    pub fn team_id(&self) -> Option<&str> {
+        match self.kind {
+            RequestKind::Interaction => self.payload["team"]["id"].as_str(),
+            RequestKind::Event | RequestKind::Command => self.payload["team_id"].as_str(),
+        }
+    }
+
+    /// Returns the id of the enterprise the request originates from, if any.
+    #[must_use]
+    pub fn enterprise_id(&self) -> Option<&str> {
+        match self.kind {
+            RequestKind::Interaction => self.payload["enterprise"]["id"]
+                .as_str()
+                .or_else(|| self.payload["team"]["enterprise_id"].as_str()),
+            RequestKind::Event | RequestKind::Command => self.payload["enterprise_id"].as_str(),
+        }
+    }
+
+    /// Returns true if the request was caused by a bot, such as messages posted by an app.
+    #[must_use]
+    pub fn is_bot(&self) -> bool {
+        let event = &self.payload["event"];
+
+        self.kind == RequestKind::Event
+            && (event["bot_id"].is_string() || event["subtype"] == "bot_message")
+    }
+
+    /// Checks if the reply to the request has to contain the result of the handler.
+    pub(super) fn needs_reply(&self) -> bool {
+        self.kind == RequestKind::Interaction && self.r#type() == Some("block_suggestion")
+    }
+
+    /// Checks if the request would be handled by the listener with the identifier.
+    fn matches(&self, identifier: &str) -> bool {
+        match self.kind {
+            RequestKind::Interaction => match self.r#type() {
+                Some("block_actions") => {
+                    self.payload["actions"].as_array().is_some_and(|actions| {
+                        actions
+                            .iter()
+                            .any(|action| action["action_id"] == identifier)
+                    })
+                }
+                Some("block_suggestion") => self.payload["action_id"] == identifier,
+                Some("view_submission" | "view_closed") => {
+                    self.payload["view"]["callback_id"] == identifier
+                }
+                _ => self.payload["callback_id"] == identifier,
+            },
+            RequestKind::Event | RequestKind::Command => self.r#type() == Some(identifier),
+        }
+    }
+}
+
+/// The rest of the middleware-chain, ending in the handler for the request.
+///
+/// A middleware can short-circuit the request by not running the chain,
+/// or act after the handler by awaiting it.
+pub struct Next<S> {
+    listeners: Arc<Listeners<S>>,
+    index: usize,
+}
+
+impl<S: Send + Sync + 'static> Next<S> {
+    /// Starts a new chain for the listeners.
+    pub(super) const fn new(listeners: Arc<Listeners<S>>) -> Self {
+        Self {
+            listeners,
+            index: 0,
+        }
+    }
+
+    /// Runs the rest of the chain, returning the body to reply with, if any.
+    pub fn run(
+        mut self,
+        incoming: Incoming,
+        context: Context<S>,
+    ) -> BoxFuture<'static, AppResult<Option<json::Value>>> {
+        let listeners = self.listeners.clone();
+
+        // Find the next middleware applying to the request
+        let next = listeners
+            .middleware
+            .iter()
+            .enumerate()
+            .skip(self.index)
+            .find(|(_, (identifier, _))| {
+                identifier
+                    .as_deref()
+                    .is_none_or(|identifier| incoming.matches(identifier))
+            });
+
+        match next {
+            Some((index, (_, middleware))) => {
+                self.index = index + 1;
+                middleware(incoming, context, self)
+            }
+            None => Box::pin(async move { listeners.route(incoming, context).await }),
+        }
+    }
+}
+
+/// A middleware ignoring events caused by bots, such as messages posted by the app itself.
+///
+/// # Errors
+///
+/// Errors from the rest of the chain are passed on.
+pub async fn ignore_bots<S: Send + Sync + 'static>(
+    incoming: Incoming,
+    context: Context<S>,
+    next: Next<S>,
+) -> AppResult<Option<json::Value>> {
+    if incoming.is_bot() {
+        return Ok(None);
+    }
+
+    next.run(incoming, context).await
+}
@@ -1,33 +1,60 @@
-use axum::http::HeaderMap;
+use axum::{
+    extract::Query,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
 use colored::Colorize;
-use futures::{future::BoxFuture, Future};
+use futures::Future;
 use serde::de::DeserializeOwned;
 use serde_json as json;
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use std::{collections::HashMap, marker::PhantomData, net::SocketAddr, sync::Arc};
+use tokio::sync::Semaphore;
 
 mod auth;
+mod context;
 mod error;
-
-pub use crate::payload::{BlockAction, MessageAction, Shortcut, ViewClosed, ViewSubmission};
+mod installation;
+mod listeners;
+mod middleware;
+mod oauth;
+mod socket;
+
+pub use crate::message::ResponseMessage;
+pub use crate::payload::{
+    Action, BlockAction, BlockSuggestion, Event, EventCallback, MessageAction, Shortcut,
+    SlashCommand, Suggestions, UrlVerification, ViewClosed, ViewSubmission,
+};
+pub use crate::view::ViewSubmissionResponse;
+use crate::{layout::HomeTab, view::View, WebClient};
 pub use auth::Auth;
-pub(crate) use error::Error;
+pub use context::Context;
+pub use error::Error;
+pub use installation::{
+    FileInstallationStore, Installation, InstallationStore, MemoryInstallationStore,
+};
+use listeners::Listeners;
+use middleware::Middleware;
+pub use middleware::{ignore_bots, Incoming, Next, RequestKind};
+pub use oauth::OAuth;
+use socket::SocketMode;
 
 /// A result from a bolt-rs App.
 #[allow(clippy::module_name_repetitions)]
 pub type AppResult<T> = Result<T, Error>;
 
-/// A collection of closures
-type Interactions<T> =
-    HashMap<String, Box<dyn Fn(T) -> BoxFuture<'static, AppResult<()>> + Send + Sync>>;
-
 /// Defines behaviour for an interaction.
 ///
 /// The identifier-functions help find the correct closure for the interaction.
 /// The error-function is used to define the error-type that the interaction should return.
 pub(crate) trait Interaction: DeserializeOwned {
-    fn identifier(&self) -> String;
+    fn identifier(&self) -> Option<String>;
     fn identifier_name() -> String;
     fn error(message: String) -> crate::app::Error;
+
+    /// The url used to respond to the interaction, if any.
+    fn response_url(&self) -> Option<String> {
+        None
+    }
 }
 
 /// The logging "system" for slack-rs.
@@ -48,42 +75,55 @@ pub(crate) trait Logger {
         let banner = format!("[WARNING][{}]", Self::name()).yellow();
         println!("{banner} {message}");
     }
+
+    /// An error log:`[ERROR][<name>] <message>`
+    fn error(message: &str) {
+        let banner = format!("[ERROR][{}]", Self::name()).red();
+        println!("{banner} {message}");
+    }
 }
 
 /// A representation of a bolt-rs app. This is the main "server"
 /// struct used for the app.
-pub struct App {
+///
+/// The app can hold a shared state of type `S`, which is available to handlers through their [`Context`].
+/// As handlers are typed on the state, `R` tracks if any have been added, which is when the
+/// state can no longer be changed.
+pub struct App<S = (), R = Routed> {
     address: SocketAddr,
     auth: Auth,
-
-    block_actions: Interactions<BlockAction>,
-    message_actions: Interactions<MessageAction>,
-    shortcuts: Interactions<Shortcut>,
-    view_closes: Interactions<ViewClosed>,
-    view_submissions: Interactions<ViewSubmission>,
+    state: Arc<S>,
+    listeners: Listeners<S>,
+    client: WebClient,
+    routes: PhantomData<R>,
 }
 
-impl Default for App {
+/// Marks an [`App`] without any handlers or middleware, where the state can still be set.
+pub struct Unrouted;
+
+/// Marks an [`App`] with handlers or middleware, which are typed on the state of the app.
+pub struct Routed;
+
+impl Default for App<(), Unrouted> {
     fn default() -> Self {
         Self {
             address: SocketAddr::from(([0, 0, 0, 0], 8080)),
             auth: Auth::default(),
-            block_actions: HashMap::new(),
-            message_actions: HashMap::new(),
-            shortcuts: HashMap::new(),
-            view_closes: HashMap::new(),
-            view_submissions: HashMap::new(),
+            state: Arc::new(()),
+            listeners: Listeners::default(),
+            client: WebClient::new(""),
+            routes: PhantomData,
         }
     }
 }
 
-impl Logger for App {
+impl<S, R> Logger for App<S, R> {
     fn name() -> String {
         "App".to_string()
     }
 }
 
-impl App {
+impl App<(), Unrouted> {
     /// Creates a new app.
     #[must_use]
     pub fn new(auth: Auth) -> Self {
@@ -93,6 +133,26 @@ impl App {
         }
     }
 
+    /// Sets the shared state of the app, available to handlers through their [`Context`].
+    ///
+    /// As handlers are typed on the state, it can only be set before adding any handlers.
+    #[must_use]
+    pub fn with_state<S>(self, state: S) -> App<S, Unrouted> {
+        App {
+            address: self.address,
+            auth: self.auth,
+            state: Arc::new(state),
+            listeners: Listeners {
+                lazy: self.listeners.lazy,
+                ..Default::default()
+            },
+            client: self.client,
+            routes: PhantomData,
+        }
+    }
+}
+
+impl<S: Send + Sync + 'static, R> App<S, R> {
     /// Sets the desired socket-address for the App to serve/listen on.
     /// Default is `0.0.0.0:8000`
     #[must_use]
@@ -101,29 +161,36 @@ impl App {
         self
     }
 
-    /// Handles incoming interactions.
-    async fn handle_interaction<T: Interaction + Send>(
-        closures: Arc<Interactions<T>>,
-        interaction: String,
-    ) -> AppResult<()> {
-        // Parse interaction
-        let interaction = match json::from_str::<T>(&interaction) {
-            Ok(i) => i,
-            Err(error) => return Err(T::error(format!("Tried to parse JSON to struct: {error}"))),
-        };
+    /// Sets the [`WebClient`] of the app, e.g. to use another base-url or retry-policy.
+    /// The client is available to handlers through [`Context::client`], using the token of each request.
+    #[must_use]
+    pub fn client(mut self, client: WebClient) -> Self {
+        self.client = client;
+        self
+    }
 
-        // Get identifier
-        let identifier = interaction.identifier();
-
-        // Get and run closure
-        match closures.get(&identifier) {
-            Some(closure) => closure(interaction).await,
-            None => Err(T::error(format!(
-                "Unknown {}: '{}'",
-                T::identifier_name(),
-                identifier
-            ))),
-        }
+    /// Makes the app acknowledge requests immediately, and run the handlers afterwards on separate tasks,
+    /// with at most `max_concurrency` handlers running at once.
+    ///
+    /// This lets handlers run for longer than the 3 seconds slack waits for an acknowledgement.
+    /// As the reply is sent before the handler has finished, anything returned from the handlers is discarded,
+    /// except for options which are still handled before replying. Use [`Context::respond`] or the
+    /// [`WebClient`](crate::WebClient) of the context to respond instead.
+    ///
+    /// Errors and panics from the handlers are reported through the log.
+    ///
+    /// # Panics
+    ///
+    /// Panics will occur if `max_concurrency` is 0.
+    #[must_use]
+    pub fn lazy(mut self, max_concurrency: usize) -> Self {
+        assert!(
+            max_concurrency > 0,
+            "Lazy listeners need a concurrency above 0!"
+        );
+
+        self.listeners.lazy = Some(Arc::new(Semaphore::new(max_concurrency)));
+        self
     }
 
     /// Creates the app and starts serving/listening on the configured address.
@@ -135,55 +202,113 @@ impl App {
         // Check for warnings
         self.run_pre_startup_checks();
 
-        // Closure bindings
-        let block_actions = Arc::new(self.block_actions);
-        let message_actions = Arc::new(self.message_actions);
-        let shortcuts = Arc::new(self.shortcuts);
-        let view_closes = Arc::new(self.view_closes);
-        let view_submissions = Arc::new(self.view_submissions);
+        // Context bindings
+        let context = Context::new(self.state, &self.auth, self.client);
+        let interaction_context = context.clone();
+        let command_context = context.clone();
+        let event_context = context;
+
+        // Listener bindings
+        let listeners = Arc::new(self.listeners);
+        let interaction_listeners = listeners.clone();
+        let command_listeners = listeners.clone();
+        let event_listeners = listeners;
+
+        // Authentication bindings
+        let interaction_auth = self.auth.clone();
+        let command_auth = self.auth.clone();
+        let oauth_auth = self.auth.clone();
+        let event_auth = self.auth;
 
         // HANDLER: Interactions
         let interaction_handler = move |headers: HeaderMap, body: String| async move {
             // Sanitize payload
-            let payload = self.auth.sanitize_payload(&body, &headers)?;
+            let payload = interaction_auth.sanitize_payload(&body, &headers)?;
+
+            // Parse payload
+            let incoming = Incoming::new(RequestKind::Interaction, json::from_str(&payload)?);
+
+            // Reply with the body from the handler, if any
+            match interaction_listeners
+                .run(incoming, interaction_context)
+                .await?
+            {
+                Some(body) => Ok::<Response, Error>(axum::Json(body).into_response()),
+                None => Ok(().into_response()),
+            }
+        };
 
-            // Parse json
-            let json: json::Map<String, json::Value> = json::from_str(&payload)?;
+        // HANDLER: Events
+        let event_handler = move |headers: HeaderMap, body: String| async move {
+            // Sanitize payload
+            let payload = event_auth.sanitize_json_payload(&body, &headers)?;
 
             // Get type
-            let r#type = match json.get("type") {
-                Some(t) => t.to_string().replace('"', ""),
-                None => {
-                    return Err(Error::Parsing(
-                        "Received new interaction without a type!".to_string(),
-                    ))
-                }
-            };
+            let r#type = Listeners::<S>::get_type(&payload)?;
 
-            Self::log(&format!("Recieved a new '{type}' interaction"));
+            Self::log(&format!("Recieved a new '{type}' event"));
 
-            // Match type of interaction to handle
+            // Match type of event to handle
             match r#type.as_str() {
-                "block_actions" | "interactive_message" => {
-                    Self::handle_interaction(block_actions, payload).await?
+                "url_verification" => {
+                    let verification = json::from_str::<UrlVerification>(&payload)?;
+                    Ok(verification.challenge)
                 }
-                "message_action" => Self::handle_interaction(message_actions, payload).await?,
-                "shortcut" => Self::handle_interaction(shortcuts, payload).await?,
-                "view_closed" => Self::handle_interaction(view_closes, payload).await?,
-                "view_submission" => Self::handle_interaction(view_submissions, payload).await?,
-
-                t => {
-                    return Err(Error::Parsing(format!(
-                        "'{t}' is not a known interaction type!"
-                    )))
+                "event_callback" => {
+                    let incoming = Incoming::new(RequestKind::Event, json::from_str(&payload)?);
+                    event_listeners.run(incoming, event_context).await?;
+                    Ok(String::new())
                 }
-            };
 
-            Ok(())
+                t => Err(Error::Parsing(format!("'{t}' is not a known event type!"))),
+            }
+        };
+
+        // HANDLER: Slash-commands
+        let command_handler = move |headers: HeaderMap, body: String| async move {
+            // Verify payload
+            command_auth.verify(&body, &headers)?;
+
+            // Parse form
+            let command = serde_urlencoded::from_str::<SlashCommand>(&body)?;
+            let incoming = Incoming::new(RequestKind::Command, json::to_value(command)?);
+
+            // Reply with the message from the handler, if any
+            match command_listeners.run(incoming, command_context).await? {
+                Some(body) => Ok::<Response, Error>(axum::Json(body).into_response()),
+                None => Ok(().into_response()),
+            }
         };
 
         // Setup routes
-        let router = axum::Router::new().route("/", axum::routing::post(interaction_handler));
+        let mut router = axum::Router::new()
+            .route("/", axum::routing::post(interaction_handler.clone()))
+            .route("/options", axum::routing::post(interaction_handler))
+            .route("/events", axum::routing::post(event_handler))
+            .route("/commands", axum::routing::post(command_handler));
+
+        // Setup routes for the OAuth installation flow
+        if let Some(oauth) = oauth_auth.get_oauth().cloned() {
+            let signing_secret = oauth_auth.signing_secret();
+            let install_oauth = oauth.clone();
+            let install_secret = signing_secret.clone();
+
+            // HANDLER: Installation
+            let install_handler = move || async move { install_oauth.install(&install_secret) };
+
+            // HANDLER: OAuth-redirect
+            let redirect_handler = move |Query(query): Query<HashMap<String, String>>,
+                                         headers: HeaderMap| async move {
+                oauth.redirect(&query, &headers, &signing_secret).await
+            };
+
+            router = router
+                .route("/slack/install", axum::routing::get(install_handler))
+                .route(
+                    "/slack/oauth_redirect",
+                    axum::routing::get(redirect_handler),
+                );
+        }
 
         // Create server
         let server = axum::Server::bind(&self.address);
@@ -197,78 +322,280 @@ impl App {
             .expect("Server crashed");
     }
 
+    /// Connects to slack through Socket Mode, and starts listening for requests.
+    ///
+    /// Socket Mode uses a WebSocket-connection opened by the app, instead of requests sent from slack
+    /// to a public address. Interactions, events and slash-commands are all received through the connection,
+    /// and handled by the same handlers as when serving with [`App::start`].
+    ///
+    /// The app-token is an app-level token with the `connections:write` scope.
+    pub async fn start_socket_mode(self, app_token: &str) {
+        // Check for warnings
+        self.auth.run_token_checks();
+
+        let context = Context::new(self.state, &self.auth, self.client);
+
+        SocketMode::new(Arc::new(self.listeners), context)
+            .start(app_token)
+            .await;
+    }
+
+    /// Connects to a WebSocket-url in Socket Mode, and starts listening for requests.
+    ///
+    /// Unlike [`App::start_socket_mode`], the url is used directly instead of being requested
+    /// through `apps.connections.open`, which makes this useful for testing against a local server.
+    pub async fn start_socket_mode_at(self, url: &str) {
+        let context = Context::new(self.state, &self.auth, self.client);
+
+        SocketMode::new(Arc::new(self.listeners), context)
+            .start_at(url)
+            .await;
+    }
+
     /// Checks basic requirements before serving/listening.
     fn run_pre_startup_checks(&self) {
         self.auth.run_pre_startup_checks();
     }
 
+    /// Marks the app as having handlers or middleware, so the state can no longer be changed.
+    fn routed(self) -> App<S, Routed> {
+        App {
+            address: self.address,
+            auth: self.auth,
+            state: self.state,
+            listeners: self.listeners,
+            client: self.client,
+            routes: PhantomData,
+        }
+    }
+
+    /// Adds a middleware to the app, running for every incoming request before it reaches a handler.
+    ///
+    /// Middleware runs in the order it was added, and is given the rest of the chain as [`Next`].
+    /// A middleware can inspect the request, add extensions to the [`Context`],
+    /// short-circuit the request by not running the chain, or act after the handler has finished.
+    #[must_use]
+    pub fn middleware<F, Fut>(mut self, cb: F) -> App<S, Routed>
+    where
+        Fut: Future<Output = AppResult<Option<json::Value>>> + Send + 'static,
+        F: Fn(Incoming, Context<S>, Next<S>) -> Fut + Send + Sync + 'static,
+    {
+        self.listeners
+            .middleware
+            .push(Self::box_middleware(None, cb));
+        self.routed()
+    }
+
+    /// Adds a middleware to the app, only running for requests handled by the listener with the identifier,
+    /// e.g. the callback-id of a shortcut, the action-id of a block-action or the name of a slash-command.
+    #[must_use]
+    pub fn middleware_for<F, Fut>(mut self, identifier: &str, cb: F) -> App<S, Routed>
+    where
+        Fut: Future<Output = AppResult<Option<json::Value>>> + Send + 'static,
+        F: Fn(Incoming, Context<S>, Next<S>) -> Fut + Send + Sync + 'static,
+    {
+        self.listeners
+            .middleware
+            .push(Self::box_middleware(Some(identifier.to_string()), cb));
+        self.routed()
+    }
+
+    /// Boxes a middleware closure.
+    fn box_middleware<F, Fut>(identifier: Option<String>, cb: F) -> Middleware<S>
+    where
+        Fut: Future<Output = AppResult<Option<json::Value>>> + Send + 'static,
+        F: Fn(Incoming, Context<S>, Next<S>) -> Fut + Send + Sync + 'static,
+    {
+        (
+            identifier,
+            Box::new(move |incoming, context, next| Box::pin(cb(incoming, context, next))),
+        )
+    }
+
     /// Adds a block-actions handler to the app.
+    ///
+    /// The handler is called for every action with a matching action-id,
+    /// and receives the full payload along with the matching [`Action`].
+    #[must_use]
+    pub fn block_actions<F, Fut>(self, action_id: &str, cb: F) -> App<S, Routed>
+    where
+        Fut: Future<Output = AppResult<()>> + Send + 'static,
+        F: Fn(BlockAction, Action, Context<S>) -> Fut + Send + Sync + 'static,
+    {
+        self.add_block_actions(None, action_id, cb)
+    }
+
+    /// Adds a block-actions handler to the app, only matching actions within the given block-id.
+    ///
+    /// Handlers constrained to a block-id takes precedence over handlers added with `block_actions`.
+    #[must_use]
+    pub fn block_actions_in<F, Fut>(self, block_id: &str, action_id: &str, cb: F) -> App<S, Routed>
+    where
+        Fut: Future<Output = AppResult<()>> + Send + 'static,
+        F: Fn(BlockAction, Action, Context<S>) -> Fut + Send + Sync + 'static,
+    {
+        self.add_block_actions(Some(block_id.to_string()), action_id, cb)
+    }
+
+    /// Inserts a block-actions handler.
+    fn add_block_actions<F, Fut>(
+        mut self,
+        block_id: Option<String>,
+        action_id: &str,
+        cb: F,
+    ) -> App<S, Routed>
+    where
+        Fut: Future<Output = AppResult<()>> + Send + 'static,
+        F: Fn(BlockAction, Action, Context<S>) -> Fut + Send + Sync + 'static,
+    {
+        self.listeners
+            .block_actions
+            .entry(action_id.to_string())
+            .or_default()
+            .push((
+                block_id,
+                Box::new(move |interaction, action, context| {
+                    Box::pin(cb(interaction, action, context))
+                }),
+            ));
+        self.routed()
+    }
+
+    /// Adds a slash-command handler to the app.
+    ///
+    /// Slash-commands are received on the `/commands` route, which should be
+    /// set as the request-url of the command.
+    ///
+    /// If the handler returns a [`ResponseMessage`], it will be sent as the reply to the command.
     #[must_use]
-    pub fn block_actions<F, Fut>(mut self, action_id: &str, cb: F) -> Self
+    pub fn command<F, Fut>(mut self, command: &str, cb: F) -> App<S, Routed>
+    where
+        Fut: Future<Output = AppResult<Option<ResponseMessage>>> + Send + 'static,
+        F: Fn(SlashCommand, Context<S>) -> Fut + Send + Sync + 'static,
+    {
+        self.listeners.commands.insert(
+            command.to_string(),
+            Box::new(move |interaction, context| Box::pin(cb(interaction, context))),
+        );
+        self.routed()
+    }
+
+    /// Adds an event handler to the app.
+    ///
+    /// Events are received on the `/events` route, which should be
+    /// set as the request-url of the apps event-subscriptions.
+    #[must_use]
+    pub fn event<F, Fut>(mut self, event_type: &str, cb: F) -> App<S, Routed>
     where
         Fut: Future<Output = AppResult<()>> + Send + 'static,
-        F: Fn(BlockAction) -> Fut + Send + Sync + 'static,
+        F: Fn(EventCallback, Context<S>) -> Fut + Send + Sync + 'static,
+    {
+        self.listeners.events.insert(
+            event_type.to_string(),
+            Box::new(move |interaction, context| Box::pin(cb(interaction, context))),
+        );
+        self.routed()
+    }
+
+    /// Adds a home-tab to the app, rendered for a user and published whenever they open the home-tab.
+    ///
+    /// This registers a handler for the `app_home_opened` event, replacing any previous handler.
+    #[must_use]
+    pub fn home<F, Fut>(self, render: F) -> App<S, Routed>
+    where
+        Fut: Future<Output = AppResult<View<HomeTab>>> + Send + 'static,
+        F: Fn(String, Context<S>) -> Fut + Send + Sync + 'static,
     {
-        self.block_actions.insert(
+        self.event("app_home_opened", move |callback, context| {
+            // Render before moving into the future, as the closure can't be moved
+            let view = match callback.event {
+                Event::AppHomeOpened(opened) if opened.tab == "home" => {
+                    Some((render(opened.user.clone(), context.clone()), opened.user))
+                }
+                _ => None,
+            };
+
+            async move {
+                if let Some((view, user)) = view {
+                    view.await?.publish(&user, &context.client()?).await?;
+                }
+                Ok(())
+            }
+        })
+    }
+
+    /// Adds an options handler to the app, for elements using external-data.
+    ///
+    /// Option-requests are received on the `/options` route, which should be
+    /// set as the options-load-url of the app.
+    #[must_use]
+    pub fn options<F, Fut>(mut self, action_id: &str, cb: F) -> App<S, Routed>
+    where
+        Fut: Future<Output = AppResult<Suggestions>> + Send + 'static,
+        F: Fn(BlockSuggestion, Context<S>) -> Fut + Send + Sync + 'static,
+    {
+        self.listeners.block_suggestions.insert(
             action_id.to_string(),
-            Box::new(move |interaction| Box::pin(cb(interaction))),
+            Box::new(move |interaction, context| Box::pin(cb(interaction, context))),
         );
-        self
+        self.routed()
     }
 
     /// Adds a message-actions handler to the app.
     #[must_use]
-    pub fn message_actions<F, Fut>(mut self, callback_id: &str, cb: F) -> Self
+    pub fn message_actions<F, Fut>(mut self, callback_id: &str, cb: F) -> App<S, Routed>
     where
         Fut: Future<Output = AppResult<()>> + Send + 'static,
-        F: Fn(MessageAction) -> Fut + Send + Sync + 'static,
+        F: Fn(MessageAction, Context<S>) -> Fut + Send + Sync + 'static,
     {
-        self.message_actions.insert(
+        self.listeners.message_actions.insert(
             callback_id.to_string(),
-            Box::new(move |interaction| Box::pin(cb(interaction))),
+            Box::new(move |interaction, context| Box::pin(cb(interaction, context))),
         );
-        self
+        self.routed()
     }
 
     /// Adds a shortcut handler to the app.
     #[must_use]
-    pub fn shortcut<F, Fut>(mut self, callback_id: &str, cb: F) -> Self
+    pub fn shortcut<F, Fut>(mut self, callback_id: &str, cb: F) -> App<S, Routed>
     where
         Fut: Future<Output = AppResult<()>> + Send + 'static,
-        F: Fn(Shortcut) -> Fut + Send + Sync + 'static,
+        F: Fn(Shortcut, Context<S>) -> Fut + Send + Sync + 'static,
     {
-        self.shortcuts.insert(
+        self.listeners.shortcuts.insert(
             callback_id.to_string(),
-            Box::new(move |interaction| Box::pin(cb(interaction))),
+            Box::new(move |interaction, context| Box::pin(cb(interaction, context))),
         );
-        self
+        self.routed()
     }
 
     /// Adds a view-closed handler to the app.
     #[must_use]
-    pub fn view_close<F, Fut>(mut self, callback_id: &str, cb: F) -> Self
+    pub fn view_close<F, Fut>(mut self, callback_id: &str, cb: F) -> App<S, Routed>
     where
         Fut: Future<Output = AppResult<()>> + Send + 'static,
-        F: Fn(ViewClosed) -> Fut + Send + Sync + 'static,
+        F: Fn(ViewClosed, Context<S>) -> Fut + Send + Sync + 'static,
     {
-        self.view_closes.insert(
+        self.listeners.view_closes.insert(
             callback_id.to_string(),
-            Box::new(move |interaction| Box::pin(cb(interaction))),
+            Box::new(move |interaction, context| Box::pin(cb(interaction, context))),
         );
-        self
+        self.routed()
     }
 
     /// Adds a view-submission handler to the app.
+    ///
+    /// If the handler returns a [`ViewSubmissionResponse`], it will be sent as the reply to the submission.
     #[must_use]
-    pub fn view_submission<F, Fut>(mut self, callback_id: &str, cb: F) -> Self
+    pub fn view_submission<F, Fut>(mut self, callback_id: &str, cb: F) -> App<S, Routed>
     where
-        Fut: Future<Output = AppResult<()>> + Send + 'static,
-        F: Fn(ViewSubmission) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = AppResult<Option<ViewSubmissionResponse>>> + Send + 'static,
+        F: Fn(ViewSubmission, Context<S>) -> Fut + Send + Sync + 'static,
     {
-        self.view_submissions.insert(
+        self.listeners.view_submissions.insert(
             callback_id.to_string(),
-            Box::new(move |interaction| Box::pin(cb(interaction))),
+            Box::new(move |interaction, context| Box::pin(cb(interaction, context))),
         );
-        self
+        self.routed()
     }
 }
@@ -0,0 +1,399 @@
+use super::{AppResult, Error, Installation, InstallationStore, Logger};
+use crate::{core::signatures_match, pre::Deserialize, WebClient};
+use axum::{
+    http::{
+        header::{COOKIE, SET_COOKIE},
+        HeaderMap, StatusCode,
+    },
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use chrono::Local;
+use hmac_sha256::HMAC;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// The time a state-parameter is valid for, in seconds.
+const STATE_EXPIRATION: i64 = 600;
+
+/// The cookie binding a state-parameter to the browser that started the installation.
+const STATE_COOKIE: &str = "slack-app-oauth-state";
+
+/// Configuration of the OAuth v2 installation flow, letting an app be installed in multiple workspaces.
+///
+/// When configured on the [`Auth`](super::Auth) of an app, the app serves the `/slack/install` and
+/// `/slack/oauth_redirect` routes, and resolves the tokens of each request from its installation.
+#[derive(Clone)]
+pub struct OAuth {
+    client_id: String,
+    client_secret: String,
+    scopes: Vec<String>,
+    user_scopes: Vec<String>,
+    redirect_uri: Option<String>,
+    store: Arc<dyn InstallationStore>,
+    client: WebClient,
+
+    /// The nonces of state-parameters that have been used, with the time they were generated.
+    used_states: Arc<Mutex<HashMap<String, i64>>>,
+}
+
+impl Logger for OAuth {
+    fn name() -> String {
+        "OAuth".to_string()
+    }
+}
+
+impl OAuth {
+    /// Creates a new OAuth configuration, persisting installations in the store.
+    #[must_use]
+    pub fn new(
+        client_id: &str,
+        client_secret: &str,
+        store: impl InstallationStore + 'static,
+    ) -> Self {
+        Self {
+            client_id: client_id.to_string(),
+            client_secret: client_secret.to_string(),
+            scopes: Vec::new(),
+            user_scopes: Vec::new(),
+            redirect_uri: None,
+            store: Arc::new(store),
+            client: WebClient::new(""),
+            used_states: Arc::default(),
+        }
+    }
+
+    /// Sets the scopes requested for the bot-token.
+    #[must_use]
+    pub fn scopes(mut self, scopes: &[&str]) -> Self {
+        self.scopes = scopes.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Sets the scopes requested for the user-token.
+    #[must_use]
+    pub fn user_scopes(mut self, scopes: &[&str]) -> Self {
+        self.user_scopes = scopes.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Sets the redirect-uri, which has to match one of the redirect-urls of the app.
+    /// Defaults to the first redirect-url configured for the app.
+    #[must_use]
+    pub fn redirect_uri(mut self, redirect_uri: &str) -> Self {
+        self.redirect_uri = Some(redirect_uri.to_string());
+        self
+    }
+
+    /// Sets the client used to call `oauth.v2.access`, e.g. to use a local mock-server for testing.
+    ///
+    /// The method authenticates with the client-id and -secret, so the client should be created without a token.
+    #[must_use]
+    pub fn client(mut self, client: WebClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Returns the store the installations are persisted in.
+    #[must_use]
+    pub fn store(&self) -> Arc<dyn InstallationStore> {
+        self.store.clone()
+    }
+
+    /// Generates a new state-parameter, signing a random nonce and the current time with the signing-secret.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if no randomness is available from the operating system.
+    pub fn generate_state(signing_secret: &str) -> AppResult<String> {
+        let mut nonce = [0; 16];
+        getrandom::getrandom(&mut nonce)
+            .map_err(|error| Error::OAuth(format!("Couldn't generate nonce: {error}")))?;
+
+        Ok(sign_state(
+            signing_secret,
+            Local::now().timestamp(),
+            &hex::encode(nonce),
+        ))
+    }
+
+    /// Creates the url of the slack authorization-page, with a state-parameter from [`OAuth::generate_state`].
+    #[must_use]
+    pub fn authorize_url(&self, state: &str) -> String {
+        let mut url = format!(
+            "https://slack.com/oauth/v2/authorize?client_id={}&scope={}&user_scope={}&state={}",
+            urlencoding::encode(&self.client_id),
+            urlencoding::encode(&self.scopes.join(",")),
+            urlencoding::encode(&self.user_scopes.join(",")),
+            urlencoding::encode(state),
+        );
+
+        if let Some(redirect_uri) = &self.redirect_uri {
+            url.push_str(&format!(
+                "&redirect_uri={}",
+                urlencoding::encode(redirect_uri)
+            ));
+        }
+
+        url
+    }
+
+    /// Verifies that a state-parameter was generated by the app, matches the state stored in the
+    /// browser that started the installation, hasn't expired and hasn't been used before.
+    ///
+    /// The state is marked as used, so it can only be verified once.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the state is invalid, expired or already used.
+    pub fn verify_state(
+        &self,
+        state: &str,
+        browser_state: Option<&str>,
+        signing_secret: &str,
+    ) -> AppResult<()> {
+        if !browser_state.is_some_and(|browser_state| signatures_match(browser_state, state)) {
+            return Err(Error::OAuth(
+                "State parameter wasn't issued to this browser!".to_string(),
+            ));
+        }
+
+        let now = Local::now().timestamp();
+        let (timestamp, nonce) = verify_state(signing_secret, state, now)?;
+
+        let Ok(mut used_states) = self.used_states.lock() else {
+            return Err(Error::OAuth("Used states are poisoned!".to_string()));
+        };
+
+        // States are only kept until they would have expired anyway
+        used_states.retain(|_, generated| now - *generated <= STATE_EXPIRATION);
+
+        if used_states.insert(nonce, timestamp).is_some() {
+            return Err(Error::OAuth(
+                "State parameter has already been used!".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Completes an installation, verifying the state-parameter with [`OAuth::verify_state`] and
+    /// exchanging the code for tokens through `oauth.v2.access`, before saving the installation in the store.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the state is invalid, the code can't be exchanged or the installation can't be saved.
+    pub async fn complete(
+        &self,
+        code: &str,
+        state: &str,
+        browser_state: Option<&str>,
+        signing_secret: &str,
+    ) -> AppResult<Installation> {
+        self.verify_state(state, browser_state, signing_secret)?;
+
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("code", code),
+        ];
+        if let Some(redirect_uri) = &self.redirect_uri {
+            form.push(("redirect_uri", redirect_uri));
+        }
+
+        let access = match self
+            .client
+            .post("oauth.v2.access")
+            .form(&form)
+            .send_raw()
+            .await
+        {
+            Ok(response) => response
+                .json::<OAuthV2Access>()
+                .await
+                .map_err(|error| error.to_string()),
+            Err(error) => Err(error.to_string()),
+        }
+        .map_err(|error| Error::OAuth(format!("Couldn't exchange code: {error}")))?;
+
+        let installation = Installation::try_from(access)?;
+        self.store.save(installation.clone()).await?;
+
+        Self::log(&format!(
+            "Installed in team {:?} (enterprise: {:?})",
+            installation.team_id, installation.enterprise_id
+        ));
+
+        Ok(installation)
+    }
+
+    /// Handles a request to `/slack/install`, redirecting to the slack authorization-page,
+    /// and storing the state-parameter in a cookie in the browser.
+    pub(super) fn install(&self, signing_secret: &str) -> Response {
+        let state = match Self::generate_state(signing_secret) {
+            Ok(state) => state,
+            Err(error) => {
+                Self::error(&error.to_string());
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+
+        (
+            [(
+                SET_COOKIE,
+                format!(
+                    "{STATE_COOKIE}={state}; Path=/slack/oauth_redirect; Max-Age={STATE_EXPIRATION}; HttpOnly; Secure; SameSite=Lax"
+                ),
+            )],
+            Redirect::to(&self.authorize_url(&state)),
+        )
+            .into_response()
+    }
+
+    /// Handles a request to `/slack/oauth_redirect`, completing the installation
+    /// and clearing the state-cookie.
+    pub(super) async fn redirect(
+        &self,
+        query: &HashMap<String, String>,
+        headers: &HeaderMap,
+        signing_secret: &str,
+    ) -> Response {
+        let result = match (query.get("code"), query.get("state")) {
+            (Some(code), Some(state)) => {
+                self.complete(code, state, state_cookie(headers), signing_secret)
+                    .await
+            }
+            _ => Err(Error::OAuth(format!(
+                "Installation was cancelled: {}",
+                query.get("error").map_or("Missing code", String::as_str)
+            ))),
+        };
+
+        let clear_cookie = [(
+            SET_COOKIE,
+            format!("{STATE_COOKIE}=; Path=/slack/oauth_redirect; Max-Age=0; HttpOnly; Secure; SameSite=Lax"),
+        )];
+
+        match result {
+            Ok(_) => (
+                clear_cookie,
+                Html("<h2>The app was installed!</h2><p>You can close this page.</p>"),
+            )
+                .into_response(),
+            Err(error) => {
+                Self::error(&error.to_string());
+
+                // Never leak the actual error to the endpoint.
+                (
+                    StatusCode::BAD_REQUEST,
+                    clear_cookie,
+                    Html("<h2>The installation failed.</h2><p>Please try again.</p>"),
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+/// Gets the state-parameter stored in the browser, from the cookies of a request.
+fn state_cookie(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .find_map(|cookie| {
+            cookie
+                .trim()
+                .strip_prefix(STATE_COOKIE)
+                .and_then(|cookie| cookie.strip_prefix('='))
+        })
+}
+
+/// Creates a state-parameter, signing the timestamp and nonce with the signing-secret.
+fn sign_state(signing_secret: &str, timestamp: i64, nonce: &str) -> String {
+    let signature = HMAC::mac(format!("oauth:{timestamp}.{nonce}"), signing_secret);
+    format!("{timestamp}.{nonce}.{}", hex::encode(signature))
+}
+
+/// Verifies that a state-parameter was generated by the app, and hasn't expired,
+/// returning the timestamp and nonce of the state.
+fn verify_state(signing_secret: &str, state: &str, now: i64) -> AppResult<(i64, String)> {
+    let mut parts = state.split('.');
+    let (Some(Ok(timestamp)), Some(nonce)) = (parts.next().map(str::parse::<i64>), parts.next())
+    else {
+        return Err(Error::OAuth("Malformed state parameter!".to_string()));
+    };
+
+    if !signatures_match(state, &sign_state(signing_secret, timestamp, nonce)) {
+        return Err(Error::OAuth("State parameter didn't match!".to_string()));
+    }
+
+    if !(0..=STATE_EXPIRATION).contains(&(now - timestamp)) {
+        return Err(Error::OAuth("State parameter has expired!".to_string()));
+    }
+
+    Ok((timestamp, nonce.to_string()))
+}
+
+/// An id-object, as found in the `oauth.v2.access` response.
+#[derive(Deserialize, Debug)]
+struct Id {
+    id: String,
+}
+
+/// The user who installed the app, as found in the `oauth.v2.access` response.
+#[derive(Deserialize, Debug)]
+struct AuthedUser {
+    id: String,
+    scope: Option<String>,
+    access_token: Option<String>,
+}
+
+/// A response from `oauth.v2.access`.
+#[derive(Deserialize, Debug)]
+struct OAuthV2Access {
+    ok: bool,
+    error: Option<String>,
+    app_id: Option<String>,
+    access_token: Option<String>,
+    scope: Option<String>,
+    bot_user_id: Option<String>,
+    team: Option<Id>,
+    enterprise: Option<Id>,
+    authed_user: Option<AuthedUser>,
+    #[serde(default)]
+    is_enterprise_install: bool,
+}
+
+impl TryFrom<OAuthV2Access> for Installation {
+    type Error = Error;
+
+    fn try_from(access: OAuthV2Access) -> AppResult<Self> {
+        if !access.ok {
+            return Err(Error::OAuth(format!(
+                "oauth.v2.access failed: {}",
+                access.error.unwrap_or_default()
+            )));
+        }
+
+        let (user_id, user_token, user_scopes) = match access.authed_user {
+            Some(user) => (Some(user.id), user.access_token, user.scope),
+            None => (None, None, None),
+        };
+
+        Ok(Self {
+            app_id: access.app_id,
+            enterprise_id: access.enterprise.map(|enterprise| enterprise.id),
+            team_id: access.team.map(|team| team.id),
+            bot_token: access.access_token,
+            bot_user_id: access.bot_user_id,
+            bot_scopes: access.scope,
+            user_id,
+            user_token,
+            user_scopes,
+            is_enterprise_install: access.is_enterprise_install,
+        })
+    }
+}
@@ -0,0 +1,232 @@
+use super::{json, AppResult, Context, Error, Incoming, Listeners, Logger, RequestKind};
+use crate::pre::{skip_serializing_none, Deserialize, Serialize};
+use futures::{SinkExt, StreamExt};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+/// The time to wait before reconnecting, after a connection has failed.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// An envelope received through a Socket Mode connection.
+#[derive(Deserialize, Debug)]
+struct Envelope {
+    r#type: String,
+    envelope_id: Option<String>,
+    payload: Option<json::Value>,
+    reason: Option<String>,
+    #[serde(default)]
+    accepts_response_payload: bool,
+}
+
+/// An acknowledgement of an [`Envelope`], optionally containing a response-payload.
+#[skip_serializing_none]
+#[derive(Serialize, Debug)]
+struct Ack {
+    envelope_id: String,
+    payload: Option<json::Value>,
+}
+
+/// A Socket Mode runner, feeding envelopes received through
+/// a WebSocket-connection to the listeners of an app.
+pub(super) struct SocketMode<S> {
+    listeners: Arc<Listeners<S>>,
+    context: Context<S>,
+}
+
+impl<S> Logger for SocketMode<S> {
+    fn name() -> String {
+        "SocketMode".to_string()
+    }
+}
+
+impl<S: Send + Sync + 'static> SocketMode<S> {
+    /// Creates a new runner for the listeners.
+    pub(super) const fn new(listeners: Arc<Listeners<S>>, context: Context<S>) -> Self {
+        Self { listeners, context }
+    }
+
+    /// Opens connections through `apps.connections.open` and runs them, reconnecting when needed.
+    pub(super) async fn start(&self, app_token: &str) {
+        loop {
+            let result = match self.open(app_token).await {
+                Ok(url) => self.connect(&url).await,
+                Err(error) => Err(error),
+            };
+
+            Self::reconnect(result).await;
+        }
+    }
+
+    /// Runs connections to the url, reconnecting when needed.
+    pub(super) async fn start_at(&self, url: &str) {
+        loop {
+            Self::reconnect(self.connect(url).await).await;
+        }
+    }
+
+    /// Prepares for reconnecting after a connection has ended.
+    async fn reconnect(result: AppResult<()>) {
+        if let Err(error) = result {
+            Self::error(&error.to_string());
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+
+        Self::log("Reconnecting.");
+    }
+
+    /// Requests a new WebSocket-url from slack.
+    async fn open(&self, app_token: &str) -> AppResult<String> {
+        match self
+            .context
+            .web_client(app_token)
+            .post("apps.connections.open")
+            .send::<String>()
+            .await
+            .and_then(crate::Response::unpack)
+        {
+            Ok(url) => Ok(url),
+            Err(error) => Err(Error::SocketMode(format!(
+                "Couldn't open connection: {error}"
+            ))),
+        }
+    }
+
+    /// Runs a connection until slack asks the app to reconnect, or the connection is closed.
+    async fn connect(&self, url: &str) -> AppResult<()> {
+        let (socket, _) = match connect_async(url).await {
+            Ok(connection) => connection,
+            Err(error) => return Err(Error::SocketMode(format!("Couldn't connect: {error}"))),
+        };
+        let (mut sink, mut stream) = socket.split();
+
+        // Acknowledgements are sent from the handler-tasks through a channel
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Message>();
+        let writer = tokio::spawn(async move {
+            while let Some(message) = receiver.recv().await {
+                if sink.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let result = loop {
+            let message = match stream.next().await {
+                Some(Ok(message)) => message,
+                Some(Err(error)) => {
+                    break Err(Error::SocketMode(format!("Connection failed: {error}")))
+                }
+                None => break Ok(()),
+            };
+
+            match message {
+                Message::Text(text) => {
+                    let envelope = match json::from_str::<Envelope>(&text) {
+                        Ok(envelope) => envelope,
+                        Err(error) => {
+                            Self::error(&format!("Couldn't parse envelope: {error}"));
+                            continue;
+                        }
+                    };
+
+                    match envelope.r#type.as_str() {
+                        "hello" => Self::log("Connected."),
+                        "disconnect" => {
+                            Self::log(&format!(
+                                "Slack requested a reconnect ({}).",
+                                envelope.reason.unwrap_or_default()
+                            ));
+                            break Ok(());
+                        }
+                        _ => self.handle(envelope, sender.clone()),
+                    }
+                }
+                Message::Close(_) => break Ok(()),
+                _ => (),
+            }
+        };
+
+        writer.abort();
+        result
+    }
+
+    /// Handles an envelope on a separate task.
+    ///
+    /// Envelopes are acknowledged as soon as they are received, unless slack accepts a
+    /// response-payload for them, in which case the result of the handler is sent with the
+    /// acknowledgement. With lazy listeners, such handlers still return before they have finished.
+    fn handle(&self, envelope: Envelope, sender: UnboundedSender<Message>) {
+        let Some(envelope_id) = envelope.envelope_id else {
+            Self::warn(&format!(
+                "Recieved a '{}' envelope without an id.",
+                envelope.r#type
+            ));
+            return;
+        };
+
+        if !envelope.accepts_response_payload {
+            Self::ack(&sender, envelope_id.clone(), None);
+        }
+
+        let listeners = self.listeners.clone();
+        let context = self.context.clone();
+
+        tokio::spawn(async move {
+            // Errors are still acknowledged, so slack doesn't retry the envelope.
+            let payload = match Self::dispatch(
+                listeners,
+                &envelope.r#type,
+                envelope.payload.unwrap_or_default(),
+                context,
+            )
+            .await
+            {
+                Ok(payload) => payload,
+                Err(error) => {
+                    Self::error(&error.to_string());
+                    None
+                }
+            };
+
+            if envelope.accepts_response_payload {
+                Self::ack(&sender, envelope_id, payload);
+            }
+        });
+    }
+
+    /// Sends an acknowledgement of an envelope, optionally containing a response-payload.
+    fn ack(sender: &UnboundedSender<Message>, envelope_id: String, payload: Option<json::Value>) {
+        match json::to_string(&Ack {
+            envelope_id,
+            payload,
+        }) {
+            Ok(ack) => {
+                // The connection might have been closed in the meantime.
+                sender.send(Message::Text(ack)).ok();
+            }
+            Err(error) => Self::error(&format!("Couldn't serialize acknowledgement: {error}")),
+        }
+    }
+
+    /// Dispatches the payload of an envelope through the middleware to the listeners.
+    async fn dispatch(
+        listeners: Arc<Listeners<S>>,
+        r#type: &str,
+        payload: json::Value,
+        context: Context<S>,
+    ) -> AppResult<Option<json::Value>> {
+        let kind = match r#type {
+            "interactive" => RequestKind::Interaction,
+            "events_api" => RequestKind::Event,
+            "slash_commands" => RequestKind::Command,
+
+            t => {
+                return Err(Error::SocketMode(format!(
+                    "'{t}' is not a known envelope type!"
+                )))
+            }
+        };
+
+        listeners.run(Incoming::new(kind, payload), context).await
+    }
+}
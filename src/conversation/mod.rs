@@ -1,19 +1,44 @@
 use crate::pre::{
-    block, element, message, skip_serializing_none, BoltResult, Deserialize, Request, Serialize,
+    block, json, message, payload, skip_serializing_none, BoltResult, Deserialize, Paginator,
+    Serialize, WebClient,
 };
 use block::Blocks;
-use element::Elements;
-use message::{AsMessage, Message};
-use reqwest::multipart::Form;
+use futures::stream::BoxStream;
+use message::{AsMessage, Attachment, Message};
+
+/// The topic or purpose of a [Conversation].
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Topic {
+    #[serde(default)]
+    pub value: String,
+    #[serde(default)]
+    pub creator: String,
+    #[serde(default)]
+    pub last_set: i64,
+}
 
 /// A slack-conversation
 #[skip_serializing_none]
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct Conversation {
     pub id: String,
+    pub name: Option<String>,
     pub created: Option<i64>,
+    pub creator: Option<String>,
+    pub is_channel: Option<bool>,
+    pub is_group: Option<bool>,
     pub is_im: Option<bool>,
+    pub is_mpim: Option<bool>,
+    pub is_private: Option<bool>,
+    pub is_archived: Option<bool>,
+    pub is_general: Option<bool>,
+    pub is_member: Option<bool>,
+    pub is_shared: Option<bool>,
+    pub is_ext_shared: Option<bool>,
     pub is_org_shared: Option<bool>,
+    pub topic: Option<Topic>,
+    pub purpose: Option<Topic>,
+    pub num_members: Option<i64>,
     pub user: Option<String>,
     pub last_read: Option<String>,
     //pub latest: Option<????>,
@@ -42,30 +67,269 @@ impl Conversation {
         Starter::default().channel(channel)
     }
 
-    /// Updates the current conversation.
+    /// Returns a new [Lister] for listing conversations, filtered by type.
+    #[must_use]
+    pub fn lister() -> Lister {
+        Lister::default()
+    }
+
+    /// Streams every public channel the app can see, requesting the pages of `conversations.list` as needed.
+    #[must_use]
+    pub fn list(client: &WebClient) -> BoxStream<'static, BoltResult<Self>> {
+        Lister::default().stream(client)
+    }
+
+    /// Gets a conversation by its id.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if the conversation can't be found.
+    ///
+    pub async fn info(id: &str, client: &WebClient) -> BoltResult<Self> {
+        client
+            .get(&format!("conversations.info?channel={}", encode(id)))
+            .send()
+            .await?
+            .unpack()
+    }
+
+    /// Creates a new public or private channel.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it, e.g. because the name is taken.
+    ///
+    pub async fn create(name: &str, is_private: bool, client: &WebClient) -> BoltResult<Self> {
+        client
+            .post("conversations.create")
+            .json(&json::json!({ "name": name, "is_private": is_private }))
+            .send()
+            .await?
+            .unpack()
+    }
+
+    /// Streams the messages of the conversation, newest first,
+    /// requesting the pages of `conversations.history` as needed.
+    #[must_use]
+    pub fn history(&self, client: &WebClient) -> BoxStream<'static, BoltResult<payload::Message>> {
+        self.read_history().stream(client)
+    }
+
+    /// Streams the replies in the thread of a message, starting with the parent message,
+    /// requesting the pages of `conversations.replies` as needed.
+    #[must_use]
+    pub fn replies(
+        &self,
+        thread_ts: &str,
+        client: &WebClient,
+    ) -> BoxStream<'static, BoltResult<payload::Message>> {
+        self.read_replies(thread_ts).stream(client)
+    }
+
+    /// Returns a new [History] for reading the messages of the conversation within a window of time.
+    #[must_use]
+    pub fn read_history(&self) -> History {
+        History::new("conversations.history", &self.id, None)
+    }
+
+    /// Returns a new [History] for reading the replies in the thread of a message within a window of time.
+    #[must_use]
+    pub fn read_replies(&self, thread_ts: &str) -> History {
+        History::new("conversations.replies", &self.id, Some(thread_ts))
+    }
+
+    /// Streams the user-ids of the members of the conversation,
+    /// requesting the pages of `conversations.members` as needed.
+    #[must_use]
+    pub fn members(&self, client: &WebClient) -> BoxStream<'static, BoltResult<String>> {
+        Paginator::new(client, "conversations.members")
+            .param("channel", &self.id)
+            .stream()
+    }
+
+    /// Updates the current conversation, by getting it from slack again.
     ///
     /// # Errors
     ///
     /// Errors will occur if the request can't be sent, or if slack rejects it because of formatting or the likes.
     ///
-    pub async fn update(self, token: &str) -> BoltResult<Self> {
-        Request::post("conversations.join", token)
-            .multipart(Form::new().text("channel", self.id))
+    pub async fn update(self, client: &WebClient) -> BoltResult<Self> {
+        Self::info(&self.id, client).await
+    }
+
+    /// Archives the conversation.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it, e.g. because it's already archived.
+    ///
+    pub async fn archive(&self, client: &WebClient) -> BoltResult<()> {
+        self.call("conversations.archive", json::json!({}), client)
+            .await
+    }
+
+    /// Unarchives the conversation.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it, e.g. because it isn't archived.
+    ///
+    pub async fn unarchive(&self, client: &WebClient) -> BoltResult<()> {
+        self.call("conversations.unarchive", json::json!({}), client)
+            .await
+    }
+
+    /// Renames the conversation, returning the renamed conversation.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it, e.g. because the name is taken.
+    ///
+    pub async fn rename(&self, name: &str, client: &WebClient) -> BoltResult<Self> {
+        self.fetch(
+            "conversations.rename",
+            json::json!({ "name": name }),
+            client,
+        )
+        .await
+    }
+
+    /// Invites users to the conversation, returning the updated conversation.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it, e.g. because a user can't be found.
+    ///
+    pub async fn invite(&self, users: &[&str], client: &WebClient) -> BoltResult<Self> {
+        self.fetch(
+            "conversations.invite",
+            json::json!({ "users": users.join(",") }),
+            client,
+        )
+        .await
+    }
+
+    /// Removes a user from the conversation.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it, e.g. because the user isn't a member.
+    ///
+    pub async fn kick(&self, user: &str, client: &WebClient) -> BoltResult<()> {
+        self.call("conversations.kick", json::json!({ "user": user }), client)
+            .await
+    }
+
+    /// Joins the conversation, returning the joined conversation.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it, e.g. because it's archived.
+    ///
+    pub async fn join(&self, client: &WebClient) -> BoltResult<Self> {
+        self.fetch("conversations.join", json::json!({}), client)
+            .await
+    }
+
+    /// Leaves the conversation.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it.
+    ///
+    pub async fn leave(&self, client: &WebClient) -> BoltResult<()> {
+        self.call("conversations.leave", json::json!({}), client)
+            .await
+    }
+
+    /// Sets the topic of the conversation, returning the updated conversation.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it, e.g. because the topic is too long.
+    ///
+    pub async fn set_topic(&self, topic: &str, client: &WebClient) -> BoltResult<Self> {
+        self.fetch(
+            "conversations.setTopic",
+            json::json!({ "topic": topic }),
+            client,
+        )
+        .await
+    }
+
+    /// Sets the purpose of the conversation, returning the updated conversation.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it, e.g. because the purpose is too long.
+    ///
+    pub async fn set_purpose(&self, purpose: &str, client: &WebClient) -> BoltResult<Self> {
+        self.fetch(
+            "conversations.setPurpose",
+            json::json!({ "purpose": purpose }),
+            client,
+        )
+        .await
+    }
+
+    /// Moves the read-cursor of the conversation to the message with the timestamp.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it.
+    ///
+    pub async fn mark(&self, ts: &str, client: &WebClient) -> BoltResult<()> {
+        self.call("conversations.mark", json::json!({ "ts": ts }), client)
+            .await
+    }
+
+    /// Closes a direct message or multi-person direct message.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the request can't be sent, or if slack rejects it.
+    ///
+    pub async fn close(&self, client: &WebClient) -> BoltResult<()> {
+        self.call("conversations.close", json::json!({}), client)
+            .await
+    }
+
+    /// Sends a request for the conversation to a method, returning the conversation of the response.
+    async fn fetch(&self, method: &str, body: json::Value, client: &WebClient) -> BoltResult<Self> {
+        client
+            .post(method)
+            .json(&self.body(body))
             .send()
             .await?
             .unpack()
     }
 
+    /// Sends a request for the conversation to a method, ignoring any value of the response.
+    async fn call(&self, method: &str, body: json::Value, client: &WebClient) -> BoltResult<()> {
+        client
+            .post(method)
+            .json(&self.body(body))
+            .send::<json::Value>()
+            .await?
+            .confirm()
+    }
+
+    /// Adds the id of the conversation to the body of a request.
+    fn body(&self, mut body: json::Value) -> json::Value {
+        body["channel"] = json::Value::from(self.id.as_str());
+        body
+    }
+
     /// Sends normal text to the opened conversation.
     ///
     /// # Errors
     ///
     /// Errors will occur if the request can't be sent, or if slack rejects it because of formatting or the likes.
     ///
-    pub async fn send_text(self, text: &str, token: &str) -> BoltResult<Self> {
-        self.as_message()?.text(text).post(token).await?;
+    pub async fn send_text(self, text: &str, client: &WebClient) -> BoltResult<Self> {
+        self.as_message()?.text(text).post(client).await?;
 
-        self.update(token).await
+        self.update(client).await
     }
 
     /// Sends blocks to the opened conversation.
@@ -74,10 +338,10 @@ impl Conversation {
     ///
     /// Errors will occur if the request can't be sent, or if slack rejects it because of formatting or the likes.
     ///
-    pub async fn send_blocks(self, blocks: Blocks, token: &str) -> BoltResult<Self> {
-        self.as_message()?.blocks(blocks).post(token).await?;
+    pub async fn send_blocks(self, blocks: Blocks, client: &WebClient) -> BoltResult<Self> {
+        self.as_message()?.blocks(blocks).post(client).await?;
 
-        self.update(token).await
+        self.update(client).await
     }
 
     /// Sends attachments to the opened conversation.
@@ -86,16 +350,173 @@ impl Conversation {
     ///
     /// Errors will occur if the request can't be sent, or if slack rejects it because of formatting or the likes.
     ///
-    pub async fn send_attachments(self, attachments: Elements, token: &str) -> BoltResult<Self> {
+    pub async fn send_attachments(
+        self,
+        attachments: Vec<Attachment>,
+        client: &WebClient,
+    ) -> BoltResult<Self> {
         self.as_message()?
             .attachments(attachments)
-            .post(token)
+            .post(client)
             .await?;
 
-        self.update(token).await
+        self.update(client).await
     }
 }
 
+/// A type of conversation, used for filtering lists of conversations.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversationType {
+    /// Public channels
+    PublicChannel,
+
+    /// Private channels
+    PrivateChannel,
+
+    /// Multi-person direct messages
+    Mpim,
+
+    /// Direct messages
+    Im,
+}
+
+impl ConversationType {
+    /// Returns the type as expected by slack, e.g. `public_channel`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::PublicChannel => "public_channel",
+            Self::PrivateChannel => "private_channel",
+            Self::Mpim => "mpim",
+            Self::Im => "im",
+        }
+    }
+}
+
+/// Lists conversations through `conversations.list`, filtered by type and archival.
+#[derive(Debug, Clone, Default)]
+pub struct Lister {
+    types: Vec<ConversationType>,
+    exclude_archived: bool,
+    team_id: Option<String>,
+}
+
+impl Lister {
+    /// Only lists conversations of the types. Default is public channels.
+    #[must_use]
+    pub fn types(mut self, types: &[ConversationType]) -> Self {
+        self.types = types.to_vec();
+        self
+    }
+
+    /// Excludes archived conversations.
+    #[must_use]
+    pub const fn exclude_archived(mut self) -> Self {
+        self.exclude_archived = true;
+        self
+    }
+
+    /// Only lists conversations of a team, required for org-wide apps.
+    #[must_use]
+    pub fn team_id(mut self, team_id: &str) -> Self {
+        self.team_id = Some(team_id.to_string());
+        self
+    }
+
+    /// Streams the conversations, requesting the pages of `conversations.list` as needed.
+    #[must_use]
+    pub fn stream(self, client: &WebClient) -> BoxStream<'static, BoltResult<Conversation>> {
+        let mut paginator = Paginator::new(client, "conversations.list");
+
+        if !self.types.is_empty() {
+            let types: Vec<&str> = self.types.iter().map(|t| t.as_str()).collect();
+            paginator = paginator.param("types", &types.join(","));
+        }
+        if self.exclude_archived {
+            paginator = paginator.param("exclude_archived", "true");
+        }
+        if let Some(team_id) = &self.team_id {
+            paginator = paginator.param("team_id", team_id);
+        }
+
+        paginator.stream()
+    }
+}
+
+/// Reads messages through `conversations.history`, or the replies of a thread through `conversations.replies`,
+/// optionally limited to a window of time.
+#[derive(Debug, Clone)]
+pub struct History {
+    method: &'static str,
+    channel: String,
+    thread_ts: Option<String>,
+    oldest: Option<String>,
+    latest: Option<String>,
+    inclusive: bool,
+}
+
+impl History {
+    /// Creates a new reader for a method and channel.
+    fn new(method: &'static str, channel: &str, thread_ts: Option<&str>) -> Self {
+        Self {
+            method,
+            channel: channel.to_string(),
+            thread_ts: thread_ts.map(ToString::to_string),
+            oldest: None,
+            latest: None,
+            inclusive: false,
+        }
+    }
+
+    /// Only reads messages after the timestamp.
+    #[must_use]
+    pub fn oldest(mut self, ts: &str) -> Self {
+        self.oldest = Some(ts.to_string());
+        self
+    }
+
+    /// Only reads messages before the timestamp.
+    #[must_use]
+    pub fn latest(mut self, ts: &str) -> Self {
+        self.latest = Some(ts.to_string());
+        self
+    }
+
+    /// Includes the messages with the `oldest` and `latest` timestamps.
+    #[must_use]
+    pub const fn inclusive(mut self) -> Self {
+        self.inclusive = true;
+        self
+    }
+
+    /// Streams the messages, requesting the pages as needed.
+    #[must_use]
+    pub fn stream(self, client: &WebClient) -> BoxStream<'static, BoltResult<payload::Message>> {
+        let mut paginator = Paginator::new(client, self.method).param("channel", &self.channel);
+
+        if let Some(thread_ts) = &self.thread_ts {
+            paginator = paginator.param("ts", thread_ts);
+        }
+        if let Some(oldest) = &self.oldest {
+            paginator = paginator.param("oldest", oldest);
+        }
+        if let Some(latest) = &self.latest {
+            paginator = paginator.param("latest", latest);
+        }
+        if self.inclusive {
+            paginator = paginator.param("inclusive", "true");
+        }
+
+        paginator.stream()
+    }
+}
+
+/// Percent-encodes a query-parameter.
+fn encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
 impl AsMessage for Conversation {
     fn as_message(&self) -> BoltResult<Message> {
         Ok(Message::new().channel(&self.id))
@@ -120,8 +541,9 @@ impl Starter {
     ///
     /// Errors will occur if the request can't be sent, or if slack rejects it because of formatting or the likes.
     ///
-    pub async fn start(self, token: &str) -> BoltResult<Conversation> {
-        Request::post("conversations.open", token)
+    pub async fn start(self, client: &WebClient) -> BoltResult<Conversation> {
+        client
+            .post("conversations.open")
             .json(&self)
             .send()
             .await?
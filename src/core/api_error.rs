@@ -0,0 +1,243 @@
+use super::json;
+use std::fmt::{Display, Formatter};
+
+/// An error-code returned by the slack Web API, as found in the `error` field of a response.
+///
+/// Common codes have their own variant, while every other code is kept as [`SlackApiError::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SlackApiError {
+    /// The token belongs to a deleted user or workspace.
+    AccountInactive,
+
+    /// The channel couldn't be found, or isn't visible to the token.
+    ChannelNotFound,
+
+    /// The message can't be deleted by the token.
+    CantDeleteMessage,
+
+    /// The message can't be updated by the token.
+    CantUpdateMessage,
+
+    /// The message was too long, or had too many blocks.
+    MsgTooLong,
+
+    /// The trigger-id has expired, as it has to be used within 3 seconds.
+    ExpiredTriggerId,
+
+    /// The server encountered a catastrophic error.
+    FatalError,
+
+    /// The hash didn't match the current hash of the view.
+    HashConflict,
+
+    /// The server encountered an internal error.
+    InternalError,
+
+    /// The arguments were invalid.
+    InvalidArguments,
+
+    /// The token is invalid.
+    InvalidAuth,
+
+    /// The blocks were invalid.
+    InvalidBlocks,
+
+    /// The pagination-cursor was invalid.
+    InvalidCursor,
+
+    /// The channel has been archived.
+    IsArchived,
+
+    /// The message couldn't be found.
+    MessageNotFound,
+
+    /// The token is missing a required scope.
+    MissingScope,
+
+    /// The name of a channel is already taken.
+    NameTaken,
+
+    /// No token was provided.
+    NotAuthed,
+
+    /// The token isn't a member of the channel.
+    NotInChannel,
+
+    /// The message had no text, blocks or attachments.
+    NoText,
+
+    /// The request was rate-limited.
+    Ratelimited,
+
+    /// The server timed out while handling the request.
+    RequestTimeout,
+
+    /// The token has been revoked.
+    TokenRevoked,
+
+    /// The user couldn't be found.
+    UserNotFound,
+
+    /// One or more users couldn't be found.
+    UsersNotFound,
+
+    /// The view couldn't be found.
+    ViewNotFound,
+
+    /// Any other error-code.
+    Other(String),
+}
+
+impl SlackApiError {
+    /// Returns the error-code as sent by slack, e.g. `channel_not_found`.
+    #[must_use]
+    pub fn code(&self) -> &str {
+        match self {
+            Self::AccountInactive => "account_inactive",
+            Self::ChannelNotFound => "channel_not_found",
+            Self::CantDeleteMessage => "cant_delete_message",
+            Self::CantUpdateMessage => "cant_update_message",
+            Self::MsgTooLong => "msg_too_long",
+            Self::ExpiredTriggerId => "expired_trigger_id",
+            Self::FatalError => "fatal_error",
+            Self::HashConflict => "hash_conflict",
+            Self::InternalError => "internal_error",
+            Self::InvalidArguments => "invalid_arguments",
+            Self::InvalidAuth => "invalid_auth",
+            Self::InvalidBlocks => "invalid_blocks",
+            Self::InvalidCursor => "invalid_cursor",
+            Self::IsArchived => "is_archived",
+            Self::MessageNotFound => "message_not_found",
+            Self::MissingScope => "missing_scope",
+            Self::NameTaken => "name_taken",
+            Self::NotAuthed => "not_authed",
+            Self::NotInChannel => "not_in_channel",
+            Self::NoText => "no_text",
+            Self::Ratelimited => "ratelimited",
+            Self::RequestTimeout => "request_timeout",
+            Self::TokenRevoked => "token_revoked",
+            Self::UserNotFound => "user_not_found",
+            Self::UsersNotFound => "users_not_found",
+            Self::ViewNotFound => "view_not_found",
+            Self::Other(code) => code,
+        }
+    }
+}
+
+impl From<&str> for SlackApiError {
+    fn from(code: &str) -> Self {
+        match code {
+            "account_inactive" => Self::AccountInactive,
+            "channel_not_found" => Self::ChannelNotFound,
+            "cant_delete_message" => Self::CantDeleteMessage,
+            "cant_update_message" => Self::CantUpdateMessage,
+            "msg_too_long" => Self::MsgTooLong,
+            "expired_trigger_id" => Self::ExpiredTriggerId,
+            "fatal_error" => Self::FatalError,
+            "hash_conflict" => Self::HashConflict,
+            "internal_error" => Self::InternalError,
+            "invalid_arguments" => Self::InvalidArguments,
+            "invalid_auth" => Self::InvalidAuth,
+            "invalid_blocks" => Self::InvalidBlocks,
+            "invalid_cursor" => Self::InvalidCursor,
+            "is_archived" => Self::IsArchived,
+            "message_not_found" => Self::MessageNotFound,
+            "missing_scope" => Self::MissingScope,
+            "name_taken" => Self::NameTaken,
+            "not_authed" => Self::NotAuthed,
+            "not_in_channel" => Self::NotInChannel,
+            "no_text" => Self::NoText,
+            "ratelimited" | "rate_limited" => Self::Ratelimited,
+            "request_timeout" => Self::RequestTimeout,
+            "token_revoked" => Self::TokenRevoked,
+            "user_not_found" => Self::UserNotFound,
+            "users_not_found" => Self::UsersNotFound,
+            "view_not_found" => Self::ViewNotFound,
+            code => Self::Other(code.to_string()),
+        }
+    }
+}
+
+impl Display for SlackApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// A failed Web API call, i.e. a response with `"ok": false`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    /// The error-code of the response.
+    pub error: SlackApiError,
+
+    /// The HTTP status-code of the response.
+    pub status: u16,
+
+    /// The messages from `response_metadata.messages`, e.g. describing invalid blocks.
+    pub messages: Vec<String>,
+
+    /// The warnings of the response, from `warning` and `response_metadata.warnings`.
+    pub warnings: Vec<String>,
+
+    /// The scope needed by the method, for `missing_scope` errors.
+    pub needed: Option<String>,
+
+    /// The scopes of the token, for `missing_scope` errors.
+    pub provided: Option<String>,
+}
+
+impl ApiError {
+    /// Creates a new [`ApiError`] with an error-code and HTTP status-code.
+    #[must_use]
+    pub fn new(error: impl Into<SlackApiError>, status: u16) -> Self {
+        Self {
+            error: error.into(),
+            status,
+            messages: Vec::new(),
+            warnings: Vec::new(),
+            needed: None,
+            provided: None,
+        }
+    }
+
+    /// Reads the messages and warnings from the `response_metadata` of a response.
+    #[must_use]
+    pub(super) fn metadata(mut self, response_metadata: Option<&json::Value>) -> Self {
+        if let Some(metadata) = response_metadata {
+            self.messages.extend(strings(&metadata["messages"]));
+            self.warnings.extend(strings(&metadata["warnings"]));
+        }
+        self
+    }
+}
+
+/// Returns the strings of a JSON-array, ignoring other values.
+fn strings(value: &json::Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_str().map(ToString::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' (HTTP {})", self.error, self.status)?;
+
+        if let Some(needed) = &self.needed {
+            write!(f, ", needed scope: {needed}")?;
+        }
+        if !self.messages.is_empty() {
+            write!(f, ", messages: {}", self.messages.join("; "))?;
+        }
+        if !self.warnings.is_empty() {
+            write!(f, ", warnings: {}", self.warnings.join("; "))?;
+        }
+
+        Ok(())
+    }
+}
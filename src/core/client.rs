@@ -0,0 +1,133 @@
+use super::{retry::RetryPolicy, Request};
+use std::{sync::OnceLock, time::Duration};
+
+/// The base-url of the slack Web API.
+const DEFAULT_BASE_URL: &str = "https://slack.com/api/";
+
+/// The HTTP-client shared by every [`WebClient`], so connections are reused between requests.
+static SHARED_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+/// Returns the shared HTTP-client.
+pub(crate) fn shared_client() -> reqwest::Client {
+    SHARED_CLIENT.get_or_init(reqwest::Client::new).clone()
+}
+
+/// A client for the slack Web API, holding the token used to authorize requests.
+///
+/// Every client shares one pooled HTTP-client by default, so clients are cheap to create and clone.
+#[derive(Debug, Clone)]
+pub struct WebClient {
+    token: String,
+    client: reqwest::Client,
+    base_url: String,
+    headers: Vec<(String, String)>,
+    timeout: Option<Duration>,
+    retry: RetryPolicy,
+}
+
+impl WebClient {
+    /// Creates a new [`WebClient`] using a bot- or user-token.
+    #[must_use]
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+            client: shared_client(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            headers: Vec::new(),
+            timeout: None,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Sets the base-url of the Web API, e.g. to use a local mock-server for testing.
+    /// Default is `https://slack.com/api/`
+    #[must_use]
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = if base_url.ends_with('/') {
+            base_url.to_string()
+        } else {
+            format!("{base_url}/")
+        };
+        self
+    }
+
+    /// Adds a header sent with every request.
+    #[must_use]
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        self.headers.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the timeout of every request, from when it's sent until the response has been read.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the retry-policy of every request, which can be overridden per request.
+    #[must_use]
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Replaces the shared HTTP-client with a custom one, e.g. to configure proxies.
+    #[must_use]
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Replaces the token used by the client, keeping the rest of its configuration.
+    #[must_use]
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = token.to_string();
+        self
+    }
+
+    /// Returns the token used by the client.
+    #[must_use]
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Returns the base-url of the Web API.
+    #[must_use]
+    pub fn get_base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Creates a new `POST` [Request] to an endpoint of the Web API.
+    #[must_use]
+    pub fn post(&self, endpoint: &str) -> Request {
+        self.request(reqwest::Method::POST, endpoint)
+    }
+
+    /// Creates a new `GET` [Request] to an endpoint of the Web API.
+    #[must_use]
+    pub fn get(&self, endpoint: &str) -> Request {
+        self.request(reqwest::Method::GET, endpoint)
+    }
+
+    /// Creates a new [Request] to an endpoint, with the token, headers, timeout and retry-policy of the client.
+    fn request(&self, method: reqwest::Method, endpoint: &str) -> Request {
+        let mut builder = self
+            .client
+            .request(method, format!("{}{endpoint}", self.base_url));
+
+        // Methods like `oauth.v2.access` are called without a token
+        if !self.token.is_empty() {
+            builder = builder.bearer_auth(&self.token);
+        }
+
+        for (key, value) in &self.headers {
+            builder = builder.header(key, value);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        Request::from_builder(builder, endpoint, self.retry.clone())
+    }
+}
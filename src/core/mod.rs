@@ -1,13 +1,17 @@
 use crate::pre::{
-    block, comp, element, json, skip_serializing_none, user, view, Deserialize, ModalResponse,
-    Response, Serialize,
+    block, comp, file, json, message, skip_serializing_none, user, view, Deserialize,
+    ModalResponse, Request, Response, Serialize,
 };
 use std::fmt::{Display, Formatter};
 
+pub mod api_error;
+pub mod client;
+pub mod paginator;
 pub mod parsing;
 pub mod payload;
 pub mod request;
 pub mod response;
+pub mod retry;
 pub mod state;
 
 pub type BoltResult<T> = Result<T, Error>;
@@ -15,6 +19,9 @@ pub type BoltResult<T> = Result<T, Error>;
 /// A Bolt-rs related error.
 #[derive(Debug)]
 pub enum Error {
+    /// Errors returned by the slack Web API.
+    Api(Box<api_error::ApiError>),
+
     /// Errors regarding blocks.
     Block(String, String),
 
@@ -58,6 +65,9 @@ pub enum Error {
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Api(error) => {
+                write!(f, "Slack API-error: {error}")
+            }
             Self::Block(r#type, error) => {
                 write!(f, "Block '{type}': {error}")
             }
@@ -101,6 +111,17 @@ impl Display for Error {
     }
 }
 
+impl Error {
+    /// Returns the error-code, if the error was returned by the slack Web API.
+    #[must_use]
+    pub const fn api_error(&self) -> Option<&api_error::SlackApiError> {
+        match self {
+            Self::Api(error) => Some(&error.error),
+            _ => None,
+        }
+    }
+}
+
 impl From<reqwest::Error> for Error {
     fn from(value: reqwest::Error) -> Self {
         Self::Request(value)
@@ -124,3 +145,13 @@ pub trait Build: Serialize {
 
     fn get_type(&self) -> String;
 }
+
+/// Compares two signatures in constant time, so the comparison doesn't leak how much of them match.
+pub(crate) fn signatures_match(signature: &str, expected: &str) -> bool {
+    signature.len() == expected.len()
+        && signature
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
@@ -0,0 +1,111 @@
+use super::{client::WebClient, BoltResult};
+use futures::{
+    stream::{self, BoxStream},
+    Stream, StreamExt, TryStreamExt,
+};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+
+/// The amount of items requested per page, unless configured otherwise.
+const DEFAULT_LIMIT: u32 = 200;
+
+/// A paginated Web API method, following `response_metadata.next_cursor` until every page has been read.
+///
+/// The items are yielded as a [`Stream`], and pages are only requested when the items of the previous page
+/// have been consumed.
+#[derive(Debug, Clone)]
+pub struct Paginator {
+    client: WebClient,
+    method: String,
+    params: Vec<(String, String)>,
+    limit: u32,
+}
+
+/// The state of a paginated stream between pages.
+struct Page<T> {
+    items: VecDeque<T>,
+    cursor: Option<String>,
+    done: bool,
+}
+
+impl Paginator {
+    /// Creates a new paginator for a method, e.g. `users.list`.
+    #[must_use]
+    pub fn new(client: &WebClient, method: &str) -> Self {
+        Self {
+            client: client.clone(),
+            method: method.to_string(),
+            params: Vec::new(),
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    /// Adds a query-parameter sent with every page-request.
+    #[must_use]
+    pub fn param(mut self, key: &str, value: &str) -> Self {
+        self.params.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the maximum amount of items requested per page.
+    #[must_use]
+    pub const fn limit(mut self, limit: u32) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Streams the items of every page.
+    #[must_use]
+    pub fn stream<T: DeserializeOwned + Send + 'static>(self) -> BoxStream<'static, BoltResult<T>> {
+        let page = Page {
+            items: VecDeque::new(),
+            cursor: None,
+            done: false,
+        };
+
+        stream::try_unfold((self, page), |(paginator, mut page)| async move {
+            // Request the next page, once the items of the current page are consumed
+            while page.items.is_empty() && !page.done {
+                let response = paginator
+                    .client
+                    .get(&paginator.endpoint(page.cursor.as_deref()))
+                    .send::<Vec<T>>()
+                    .await?;
+
+                page.cursor = response.next_cursor();
+                page.done = page.cursor.is_none();
+                page.items = response.unpack()?.into();
+            }
+
+            Ok(page.items.pop_front().map(|item| (item, (paginator, page))))
+        })
+        .boxed()
+    }
+
+    /// Creates the endpoint of a page-request, with the query-parameters and cursor.
+    fn endpoint(&self, cursor: Option<&str>) -> String {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+
+        query.extend_pairs(&self.params);
+        query.append_pair("limit", &self.limit.to_string());
+        if let Some(cursor) = cursor {
+            query.append_pair("cursor", cursor);
+        }
+
+        format!("{}?{}", self.method, query.finish())
+    }
+}
+
+/// Collects every item of a paginated stream, stopping after `max` items if set.
+///
+/// Pages beyond the limit are never requested.
+///
+/// # Errors
+///
+/// An error will occur if any of the page-requests fail.
+pub async fn collect_all<T>(
+    stream: impl Stream<Item = BoltResult<T>>,
+    max: Option<usize>,
+) -> BoltResult<Vec<T>> {
+    stream.take(max.unwrap_or(usize::MAX)).try_collect().await
+}
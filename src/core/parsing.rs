@@ -26,3 +26,30 @@ pub trait SerializeDefaultPhantomData {
         std::marker::PhantomData::<Self>
     }
 }
+
+/// Used internally to deserialize an optional number, which slack may send as a string,
+/// e.g. a timestamp like `"1700000000.123456"`.
+///
+/// # Errors
+///
+/// Errors will occur if the value is neither a number, nor a string containing one.
+///
+pub fn number_or_string<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(f64),
+        String(String),
+    }
+
+    match Option::<NumberOrString>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(NumberOrString::Number(number)) => Ok(Some(number)),
+        Some(NumberOrString::String(string)) => {
+            string.parse().map(Some).map_err(serde::de::Error::custom)
+        }
+    }
+}
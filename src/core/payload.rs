@@ -1,9 +1,10 @@
 use super::{
-    block, comp, element, skip_serializing_none, state, user, view, Deserialize, ModalResponse,
-    Serialize,
+    block, comp, file, json, message, skip_serializing_none, state, user, view, BoltResult,
+    Deserialize, ModalResponse, Serialize,
 };
 use crate::app::{Error as AppError, Interaction};
-use comp::{Any, Text};
+use comp::{option, Any, Text};
+use message::ResponseMessage;
 use user::Team;
 use view::View;
 
@@ -17,17 +18,36 @@ pub struct ResponseUrl {
     response_url: String,
 }
 
+/// A message, as found in interactions and the history of a conversation.
+#[skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Message {
     pub r#type: String,
-    pub user: String,
+    pub subtype: Option<String>,
+    pub user: Option<String>,
+    pub bot_id: Option<String>,
     pub ts: String,
+    pub thread_ts: Option<String>,
+    pub reply_count: Option<i64>,
+    pub reply_users_count: Option<i64>,
+    pub latest_reply: Option<String>,
     pub text: Option<String>,
     pub blocks: Option<block::Blocks>,
-    pub attachments: Option<element::Elements>,
+    pub attachments: Option<Vec<message::Attachment>>,
+    pub reactions: Option<Vec<Reaction>>,
+    pub files: Option<Vec<file::File>>,
     pub channel: Option<String>,
 }
 
+/// A reaction on a [Message].
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Reaction {
+    pub name: String,
+    pub count: i64,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Channel {
@@ -45,11 +65,24 @@ pub struct Channel {
     pub priority: Option<i32>,
 }
 
+/// The container an interaction originated from.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Container {
+    pub r#type: String,
+    pub view_id: Option<String>,
+    pub message_ts: Option<String>,
+    pub channel_id: Option<String>,
+    pub is_ephemeral: Option<bool>,
+}
+
+/// An action performed on an interactive element.
+#[skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Action {
     pub action_id: String,
     pub block_id: Option<String>,
-    pub text: Text<Any>,
+    pub text: Option<Text<Any>>,
     pub value: Option<String>,
     pub r#type: String,
     pub action_ts: String,
@@ -57,30 +90,39 @@ pub struct Action {
 
 // Payloads
 
+/// A payload sent from slack when a user interacts with an element in a block.
+#[skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct BlockAction {
     pub r#type: String,
     pub trigger_id: String,
-    pub response_url: String,
+    pub response_url: Option<String>,
     pub user: ResponseUser,
+    pub team: Option<Team>,
+    pub container: Option<Container>,
+    pub channel: Option<Channel>,
     pub message: Option<Message>,
     pub view: Option<View>,
     pub actions: Vec<Action>,
-    pub hash: String,
+    pub hash: Option<String>,
     pub state: Option<state::State>,
 }
 impl Interaction for BlockAction {
-    fn identifier(&self) -> String {
-        self.trigger_id.clone()
+    fn identifier(&self) -> Option<String> {
+        self.actions.first().map(|action| action.action_id.clone())
     }
 
     fn identifier_name() -> String {
-        "trigger_id".to_string()
+        "action_id".to_string()
     }
 
     fn error(message: String) -> crate::app::Error {
         AppError::BlockAction(message)
     }
+
+    fn response_url(&self) -> Option<String> {
+        self.response_url.clone()
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -109,8 +151,8 @@ pub struct MessageAction {
     // pub token: String,
 }
 impl Interaction for MessageAction {
-    fn identifier(&self) -> String {
-        self.callback_id.clone()
+    fn identifier(&self) -> Option<String> {
+        Some(self.callback_id.clone())
     }
 
     fn identifier_name() -> String {
@@ -120,6 +162,10 @@ impl Interaction for MessageAction {
     fn error(message: String) -> crate::app::Error {
         AppError::MessageAction(message)
     }
+
+    fn response_url(&self) -> Option<String> {
+        Some(self.response_url.clone())
+    }
 }
 
 /// A payload sent from slack for app-shortcuts.
@@ -146,8 +192,8 @@ pub struct Shortcut {
     pub user: ResponseUser,
 }
 impl Interaction for Shortcut {
-    fn identifier(&self) -> String {
-        self.callback_id.clone()
+    fn identifier(&self) -> Option<String> {
+        Some(self.callback_id.clone())
     }
 
     fn identifier_name() -> String {
@@ -179,20 +225,27 @@ pub struct ViewSubmission {
 
     pub hash: Option<String>,
 
+    #[serde(default)]
     pub response_urls: Vec<ResponseUrl>,
 }
 impl Interaction for ViewSubmission {
-    fn identifier(&self) -> String {
-        todo!()
+    fn identifier(&self) -> Option<String> {
+        self.view.get_callback_id().cloned()
     }
 
     fn identifier_name() -> String {
-        todo!()
+        "callback_id".to_string()
     }
 
     fn error(message: String) -> crate::app::Error {
         AppError::ViewSubmission(message)
     }
+
+    fn response_url(&self) -> Option<String> {
+        self.response_urls
+            .first()
+            .map(|response_url| response_url.response_url.clone())
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -204,15 +257,336 @@ pub struct ViewClosed {
     pub is_cleared: bool,
 }
 impl Interaction for ViewClosed {
-    fn identifier(&self) -> String {
-        todo!()
+    fn identifier(&self) -> Option<String> {
+        self.view.get_callback_id().cloned()
     }
 
     fn identifier_name() -> String {
-        todo!()
+        "callback_id".to_string()
     }
 
     fn error(message: String) -> crate::app::Error {
         AppError::ViewClosed(message)
     }
 }
+
+/// A payload sent from slack when verifying the events request-url.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct UrlVerification {
+    /// The type of payload. This will always be "url_verification" for this type of payload.
+    pub r#type: String,
+
+    /// The challenge that should be sent back to slack.
+    pub challenge: String,
+}
+
+/// A payload sent from slack through the Events API.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EventCallback {
+    /// The type of payload. This will always be "event_callback" for this type of payload.
+    pub r#type: String,
+
+    /// The team (Workspace) the event originates from.
+    pub team_id: String,
+
+    /// The enterprise the event originates from, if any.
+    pub enterprise_id: Option<String>,
+
+    /// The id of the app the event is sent to.
+    pub api_app_id: String,
+
+    /// The event itself.
+    pub event: Event,
+
+    /// A unique identifier for the event.
+    pub event_id: String,
+
+    /// An epoch timestamp for when the event was dispatched.
+    pub event_time: i64,
+}
+impl Interaction for EventCallback {
+    fn identifier(&self) -> Option<String> {
+        Some(self.event.r#type()).filter(|r#type| !r#type.is_empty())
+    }
+
+    fn identifier_name() -> String {
+        "event type".to_string()
+    }
+
+    fn error(message: String) -> crate::app::Error {
+        AppError::Event(message)
+    }
+}
+
+/// An event from the Events API.
+///
+/// Events without a typed representation are kept as raw json in [`Event::Other`].
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    /// A user opened the app-home.
+    AppHomeOpened(AppHomeOpened),
+
+    /// The app was mentioned in a message.
+    AppMention(AppMention),
+
+    /// A message was posted in a channel.
+    Message(MessageEvent),
+
+    /// A reaction was added to an item.
+    ReactionAdded(ReactionEvent),
+
+    /// A reaction was removed from an item.
+    ReactionRemoved(ReactionEvent),
+
+    /// Any other event, as raw json.
+    #[serde(untagged)]
+    Other(json::Value),
+}
+
+impl Event {
+    /// Returns the type of the event, e.g. `app_mention`.
+    #[must_use]
+    pub fn r#type(&self) -> String {
+        match self {
+            Self::AppHomeOpened(_) => "app_home_opened".to_string(),
+            Self::AppMention(_) => "app_mention".to_string(),
+            Self::Message(_) => "message".to_string(),
+            Self::ReactionAdded(_) => "reaction_added".to_string(),
+            Self::ReactionRemoved(_) => "reaction_removed".to_string(),
+            Self::Other(value) => value
+                .get("type")
+                .and_then(json::Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+}
+
+/// An event sent when a user opens the app-home.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppHomeOpened {
+    /// The id of the user who opened the app-home.
+    pub user: String,
+
+    /// The id of the direct-message channel between the user and the app.
+    pub channel: String,
+
+    /// The tab that was opened, either "home" or "messages".
+    pub tab: String,
+
+    /// A timestamp of when the event occurred.
+    pub event_ts: String,
+}
+
+/// An event sent when the app is mentioned in a message.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppMention {
+    /// The id of the user who mentioned the app.
+    pub user: String,
+
+    /// The text of the message, including the mention.
+    pub text: String,
+
+    /// The timestamp of the message.
+    pub ts: String,
+
+    /// The channel the message was posted in.
+    pub channel: String,
+
+    /// The timestamp of the parent message, if the message was posted in a thread.
+    pub thread_ts: Option<String>,
+
+    /// A timestamp of when the event occurred.
+    pub event_ts: String,
+}
+
+/// An event sent when a message is posted in a channel the app is subscribed to.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MessageEvent {
+    /// The subtype of the message, e.g. "bot_message" or "message_changed".
+    pub subtype: Option<String>,
+
+    /// The id of the user who posted the message, if posted by a user.
+    pub user: Option<String>,
+
+    /// The id of the bot who posted the message, if posted by a bot.
+    pub bot_id: Option<String>,
+
+    /// The text of the message.
+    pub text: Option<String>,
+
+    /// The timestamp of the message.
+    pub ts: String,
+
+    /// The channel the message was posted in.
+    pub channel: String,
+
+    /// The type of the channel, e.g. "channel", "group" or "im".
+    pub channel_type: Option<String>,
+
+    /// The timestamp of the parent message, if the message was posted in a thread.
+    pub thread_ts: Option<String>,
+
+    /// A timestamp of when the event occurred.
+    pub event_ts: String,
+}
+
+/// An event sent when a reaction is added or removed from an item.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReactionEvent {
+    /// The id of the user who added or removed the reaction.
+    pub user: String,
+
+    /// The name of the emoji, without colons.
+    pub reaction: String,
+
+    /// The id of the user who created the item, if any.
+    pub item_user: Option<String>,
+
+    /// The item the reaction was added to or removed from.
+    pub item: ReactionItem,
+
+    /// A timestamp of when the event occurred.
+    pub event_ts: String,
+}
+
+/// The item a reaction was added to or removed from.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReactionItem {
+    /// The type of the item, e.g. "message" or "file".
+    pub r#type: String,
+
+    /// The channel of the message, if the item is a message.
+    pub channel: Option<String>,
+
+    /// The timestamp of the message, if the item is a message.
+    pub ts: Option<String>,
+
+    /// The id of the file, if the item is a file.
+    pub file: Option<String>,
+}
+
+/// A payload sent from slack when a slash-command is invoked.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SlashCommand {
+    /// The command that was invoked, e.g. `/deploy`.
+    pub command: String,
+
+    /// The text written after the command.
+    #[serde(default)]
+    pub text: String,
+
+    /// The id of the user who invoked the command.
+    pub user_id: String,
+
+    /// The name of the user who invoked the command.
+    pub user_name: Option<String>,
+
+    /// The channel the command was invoked in.
+    pub channel_id: String,
+
+    /// The name of the channel the command was invoked in.
+    pub channel_name: Option<String>,
+
+    /// The team (Workspace) the command originates from.
+    pub team_id: String,
+
+    /// The domain of the team (Workspace) the command originates from.
+    pub team_domain: Option<String>,
+
+    /// The enterprise the command originates from, if any.
+    pub enterprise_id: Option<String>,
+
+    /// The id of the app the command is sent to.
+    pub api_app_id: Option<String>,
+
+    /// An url that can be used to send follow-up responses to the command.
+    pub response_url: String,
+
+    /// An id created for the interaction itself. This can be used to open modals.
+    pub trigger_id: String,
+}
+impl Interaction for SlashCommand {
+    fn identifier(&self) -> Option<String> {
+        Some(self.command.clone())
+    }
+
+    fn identifier_name() -> String {
+        "command".to_string()
+    }
+
+    fn error(message: String) -> crate::app::Error {
+        AppError::SlashCommand(message)
+    }
+
+    fn response_url(&self) -> Option<String> {
+        Some(self.response_url.clone())
+    }
+}
+impl SlashCommand {
+    /// Sends a follow-up response to the `response_url` of the command.
+    ///
+    /// This can be used to respond after the 3 second deadline of the initial reply.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack rejects the response.
+    ///
+    pub async fn respond(&self, message: &ResponseMessage) -> BoltResult<()> {
+        message.send(&self.response_url).await
+    }
+}
+
+/// A payload sent from slack when an element with external-data requests options.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BlockSuggestion {
+    /// The type of interaction used. This will always be "block_suggestion" for this type of payload.
+    pub r#type: String,
+
+    /// The action-id of the element requesting options.
+    pub action_id: String,
+
+    /// The block-id of the block containing the element.
+    pub block_id: String,
+
+    /// The query typed by the user.
+    #[serde(default)]
+    pub value: String,
+
+    /// The user who interacted.
+    pub user: ResponseUser,
+
+    /// The team (Workspace) the interaction originates from.
+    pub team: Team,
+
+    /// The container of the element, e.g. a view or a message.
+    pub container: Option<Container>,
+
+    /// The view containing the element, if any.
+    pub view: Option<View>,
+}
+impl Interaction for BlockSuggestion {
+    fn identifier(&self) -> Option<String> {
+        Some(self.action_id.clone())
+    }
+
+    fn identifier_name() -> String {
+        "action_id".to_string()
+    }
+
+    fn error(message: String) -> crate::app::Error {
+        AppError::BlockSuggestion(message)
+    }
+}
+
+/// The response to a [`BlockSuggestion`].
+pub type Suggestions = option::List;
@@ -1,31 +1,47 @@
-use super::{BoltResult, Response, Serialize};
-use reqwest::{multipart::Form, Client};
+use super::{
+    client::WebClient,
+    retry::{RetryPolicy, Tier},
+    BoltResult, Response, Serialize,
+};
+use reqwest::{header::RETRY_AFTER, multipart::Form, StatusCode};
 use serde::de::DeserializeOwned;
+use std::time::Duration;
 
 /// A request to the Slack-API.
-pub struct Request(reqwest::RequestBuilder);
+pub struct Request {
+    builder: reqwest::RequestBuilder,
+    method: String,
+    retry: RetryPolicy,
+}
 
 impl Request {
     /// Creates a new [Request] with the `POST` http-method.
+    ///
+    /// This is a shorthand for [`WebClient::post`] with a default client.
     #[must_use]
     pub fn post(endpoint: &str, token: &str) -> Self {
-        let client = Client::new();
-        Self(
-            client
-                .post(format!("https://slack.com/api/{endpoint}"))
-                .bearer_auth(token),
-        )
+        WebClient::new(token).post(endpoint)
     }
 
     /// Creates a new [Request] with the `GET` http-method.
+    ///
+    /// This is a shorthand for [`WebClient::get`] with a default client.
     #[must_use]
     pub fn get(endpoint: &str, token: &str) -> Self {
-        let client = Client::new();
-        Self(
-            client
-                .get(format!("https://slack.com/api/{endpoint}"))
-                .bearer_auth(token),
-        )
+        WebClient::new(token).get(endpoint)
+    }
+
+    /// Wraps a request-builder, already pointed at the endpoint of a method.
+    pub(crate) fn from_builder(
+        builder: reqwest::RequestBuilder,
+        method: &str,
+        retry: RetryPolicy,
+    ) -> Self {
+        Self {
+            builder,
+            method: method.to_string(),
+            retry,
+        }
     }
 
     /// Adds a json-body to the request.
@@ -35,31 +51,115 @@ impl Request {
     /// Serialization can fail if T's implementation of Serialize decides to fail, or if T contains a map with non-string keys
     #[must_use]
     pub fn json<T: Serialize + ?Sized>(mut self, json: &T) -> Self {
-        self.0 = self.0.json(json);
+        self.builder = self.builder.json(json);
+        self
+    }
+
+    /// Adds a url-encoded form-body to the request.
+    #[must_use]
+    pub fn form<T: Serialize + ?Sized>(mut self, form: &T) -> Self {
+        self.builder = self.builder.form(form);
         self
     }
 
     /// Adds a multipart form to the request
+    ///
+    /// **Disclaimer**: Requests with a multipart form can't be retried.
     #[must_use]
     pub fn multipart(mut self, form: Form) -> Self {
-        self.0 = self.0.multipart(form);
+        self.builder = self.builder.multipart(form);
         self
     }
 
     /// Adds a header to the request
     #[must_use]
     pub fn header(mut self, key: &str, value: &str) -> Self {
-        self.0 = self.0.header(key, value);
+        self.builder = self.builder.header(key, value);
+        self
+    }
+
+    /// Sets the retry-policy of the request, replacing the one from the [`WebClient`].
+    #[must_use]
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
         self
     }
 
-    /// Sends the request
+    /// Retries server errors and timeouts as well, for requests that are safe to send more than once.
+    ///
+    /// See [`RetryPolicy::idempotent`].
+    #[must_use]
+    pub fn idempotent(mut self) -> Self {
+        self.retry = self.retry.idempotent();
+        self
+    }
+
+    /// Sends the request only once, e.g. for latency-sensitive requests.
+    #[must_use]
+    pub fn no_retry(self) -> Self {
+        self.retry(RetryPolicy::none())
+    }
+
+    /// Sends the request, retrying according to the retry-policy.
     ///
     /// # Errors
     ///
     /// If the request can't be sent, an error will be returned.
     /// Any other API-related errors will be included in the [Response].
     pub async fn send<V: DeserializeOwned>(self) -> BoltResult<Response<V>> {
-        Response::from_response(self.0.send().await?).await
+        Response::from_response(self.send_raw().await?).await
+    }
+
+    /// Sends the request, retrying according to the retry-policy, and returns the HTTP-response as is.
+    ///
+    /// # Errors
+    ///
+    /// If the request can't be sent, an error will be returned.
+    pub(crate) async fn send_raw(self) -> BoltResult<reqwest::Response> {
+        let tier = Tier::of(&self.method);
+        let mut attempt = 1;
+
+        loop {
+            // The last attempt, and requests that can't be cloned, are sent as is
+            let Some(request) = self
+                .builder
+                .try_clone()
+                .filter(|_| attempt < self.retry.get_max_attempts())
+            else {
+                return Ok(self.builder.send().await?);
+            };
+
+            let delay = match request.send().await {
+                Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => self
+                    .retry
+                    .rate_limit_delay(Self::retry_after(&response), tier),
+                Ok(response)
+                    if response.status().is_server_error() && self.retry.get_server_errors() =>
+                {
+                    self.retry.backoff(attempt)
+                }
+                Ok(response) => return Ok(response),
+                // Connection-errors happen before the request is sent, so it's always safe to retry them
+                Err(error)
+                    if error.is_connect() || (error.is_timeout() && self.retry.get_timeouts()) =>
+                {
+                    self.retry.backoff(attempt)
+                }
+                Err(error) => return Err(error.into()),
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Gets the delay from the `Retry-After` header of a response, if any.
+    fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+        response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
     }
 }
@@ -1,4 +1,4 @@
-use super::{json, BoltResult, Deserialize, Error, Serialize};
+use super::{api_error::ApiError, json, BoltResult, Deserialize, Error, Serialize};
 use json::Map;
 
 /// A response from slack containing either an error or a value.
@@ -10,62 +10,103 @@ pub struct Response<V> {
     #[serde(alias = "user")]
     #[serde(alias = "profile")]
     #[serde(alias = "members")]
+    #[serde(alias = "channels")]
+    #[serde(alias = "messages")]
     #[serde(alias = "message")]
     #[serde(alias = "file")]
     #[serde(alias = "files")]
     #[serde(alias = "channel")]
+    #[serde(alias = "url")]
+    #[serde(alias = "scheduled_messages")]
     value: Option<V>,
 
     // Conversation specific
     no_op: Option<bool>,
     already_open: Option<bool>,
 
+    // Message specific
+    message_ts: Option<String>,
+    permalink: Option<String>,
+    scheduled_message_id: Option<String>,
+    post_at: Option<i64>,
+
     // Misc
     ts: Option<String>,
     cache_ts: Option<i64>,
     error: Option<String>,
+    warning: Option<String>,
+    needed: Option<String>,
+    provided: Option<String>,
     response_metadata: Option<json::Value>,
+
+    /// The HTTP status-code of the response.
+    #[serde(skip)]
+    status: u16,
 }
 
+/// The maximum amount of characters of a body included in errors.
+const SNIPPET_LENGTH: usize = 200;
+
 impl<V: serde::de::DeserializeOwned> Response<V> {
     /// Tries to create a new instance of [`Response`] from a `reqwest::Response`
     ///
     /// # Errors
     ///
-    /// Errors will occur
+    /// Errors will occur if the body can't be read, or isn't a valid response.
     pub async fn from_response(resp: reqwest::Response) -> BoltResult<Self> {
+        let status = resp.status().as_u16();
+
+        match resp.text().await {
+            Ok(body) => Self::from_body(&body, status),
+            Err(error) => Err(Error::Response(
+                std::any::type_name::<V>().to_string(),
+                format!("Couldn't read body (HTTP {status}): {error}"),
+            )),
+        }
+    }
+
+    /// Tries to create a new instance of [`Response`] from the body and HTTP status-code of a response.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the body isn't a JSON-object, or doesn't match the response of `V`.
+    /// The errors contain the status-code and the start of the body.
+    pub fn from_body(body: &str, status: u16) -> BoltResult<Self> {
+        let error = |error: String| {
+            Error::Response(
+                std::any::type_name::<V>().to_string(),
+                format!("{error} (HTTP {status}): {}", snippet(body)),
+            )
+        };
+
+        let mut map: Map<String, json::Value> = json::from_str(body)
+            .map_err(|e| error(format!("Couldn't parse body to a JSON-object: {e}")))?;
+
         // Stupid fix for channel reappearing in `Response<Message>` with a String value, instead of a struct
         // as it is in `Response<Conversation>`. Here we just remove the key from the root-json, put it into the "message" object
         // and then deserialize it into the struct.
         // TODO: REWRITE!!!!!!! Find another solution to this - There has to be an easier way.
-        let mut map: Map<String, json::Value> =
-            json::from_str(&resp.text().await.expect("No body on response?!"))
-                .expect("Couldn't parse response body to JSON.");
-
-        if let Some(c) = map.get("channel") {
-            let channel: json::Value;
-            if c.is_string() {
-                channel = map
-                    .remove("channel")
-                    .expect("Couldn't remove 'channel' key on response-json?!");
-                if let Some(m) = map.get_mut("message") {
-                    if let Some(message) = m.as_object_mut() {
-                        message.insert("channel".to_string(), channel);
-                    }
+        if map.get("channel").is_some_and(json::Value::is_string) {
+            if let Some(channel) = map.remove("channel") {
+                if let Some(message) = map.get_mut("message").and_then(json::Value::as_object_mut) {
+                    message.insert("channel".to_string(), channel);
                 }
             }
         }
-        // This implementation may be rewritten
-        // Maybe we could check the response for the user, before returning.
-        // Don't know how to construct the error yet. s
-        // How do you access an enum variant through generics?
-        match json::from_value(json::Value::from(map)) {
-            Ok(t) => Ok(t),
-            Err(error) => Err(Error::Response(
-                std::any::type_name::<V>().to_string(),
-                error.to_string(),
-            )),
-        }
+
+        let mut response = json::from_value::<Self>(json::Value::from(map))
+            .map_err(|e| error(format!("Couldn't parse response: {e}")))?;
+        response.status = status;
+
+        Ok(response)
+    }
+}
+
+/// Returns the start of a body, cut at a character-boundary.
+fn snippet(body: &str) -> String {
+    match body.char_indices().nth(SNIPPET_LENGTH) {
+        Some((index, _)) => format!("{}...", &body[..index]),
+        None => body.to_string(),
     }
 }
 
@@ -75,17 +116,76 @@ impl<V> Response<V> {
         self.ok
     }
 
-    /// Formats the error, metadata and timestamp into a readable string.
-    fn format_error(self) -> String {
-        format!(
-            "{:?}\nMetadata: {:#?}\nTimestamp: {:?}",
-            self.error, self.response_metadata, self.cache_ts
-        )
+    /// Returns the cursor of the next page, if the response is paginated and has more pages.
+    #[must_use]
+    pub fn next_cursor(&self) -> Option<String> {
+        self.response_metadata
+            .as_ref()
+            .and_then(|metadata| metadata["next_cursor"].as_str())
+            .filter(|cursor| !cursor.is_empty())
+            .map(ToString::to_string)
     }
 
-    /// Returns the requested value as an option.
-    fn value(self) -> Option<V> {
-        self.value
+    /// Returns the timestamp of the response, e.g. of a posted message.
+    #[must_use]
+    pub fn ts(&self) -> Option<&str> {
+        self.ts.as_deref()
+    }
+
+    /// Returns the timestamp of an ephemeral message.
+    #[must_use]
+    pub fn message_ts(&self) -> Option<&str> {
+        self.message_ts.as_deref()
+    }
+
+    /// Returns the permalink of a message.
+    #[must_use]
+    pub fn permalink(&self) -> Option<&str> {
+        self.permalink.as_deref()
+    }
+
+    /// Returns the id of a scheduled message.
+    #[must_use]
+    pub fn scheduled_message_id(&self) -> Option<&str> {
+        self.scheduled_message_id.as_deref()
+    }
+
+    /// Returns the unix-timestamp a scheduled message will be posted at.
+    #[must_use]
+    pub const fn post_at(&self) -> Option<i64> {
+        self.post_at
+    }
+
+    /// Returns the HTTP status-code of the response.
+    #[must_use]
+    pub const fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the error of the response, if it's not OK.
+    #[must_use]
+    pub fn api_error(&self) -> Option<ApiError> {
+        if self.ok {
+            return None;
+        }
+
+        let mut error = ApiError::new(self.error.as_deref().unwrap_or_default(), self.status)
+            .metadata(self.response_metadata.as_ref());
+        error.warnings.extend(
+            self.warning
+                .iter()
+                .flat_map(|warning| warning.split(',').map(ToString::to_string)),
+        );
+        error.needed.clone_from(&self.needed);
+        error.provided.clone_from(&self.provided);
+
+        Some(error)
+    }
+
+    /// Returns the requested value, if any.
+    #[must_use]
+    pub const fn value(&self) -> Option<&V> {
+        self.value.as_ref()
     }
 
     /// Unwraps the response to a result containing the value, if any.
@@ -95,14 +195,11 @@ impl<V> Response<V> {
     /// Errors will occur if the response is not OK or has no return-value
     pub fn unpack(self) -> BoltResult<V> {
         // Check for errors
-        if !self.is_ok() {
-            return Err(Error::Response(
-                std::any::type_name::<V>().to_string(),
-                self.format_error(),
-            ));
+        if let Some(error) = self.api_error() {
+            return Err(Error::Api(Box::new(error)));
         }
 
-        if let Some(value) = self.value() {
+        if let Some(value) = self.value {
             return Ok(value);
         }
 
@@ -111,4 +208,16 @@ impl<V> Response<V> {
             "Recieved an OK response and an empty value?!".to_string(),
         ))
     }
+
+    /// Checks that the response is OK, ignoring any value.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the response is not OK.
+    pub fn confirm(&self) -> BoltResult<()> {
+        match self.api_error() {
+            Some(error) => Err(Error::Api(Box::new(error))),
+            None => Ok(()),
+        }
+    }
 }
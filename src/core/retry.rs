@@ -0,0 +1,189 @@
+use std::{collections::hash_map::RandomState, hash::BuildHasher, time::Duration};
+
+/// The rate-limit tier of a Web API method, as documented by slack.
+///
+/// The tier decides how long to wait before retrying a rate-limited request,
+/// when slack doesn't include a `Retry-After` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tier {
+    /// 1+ requests per minute.
+    Tier1,
+
+    /// 20+ requests per minute.
+    Tier2,
+
+    /// 50+ requests per minute.
+    Tier3,
+
+    /// 100+ requests per minute.
+    Tier4,
+
+    /// Methods with their own limits, such as 1 message per second per channel for `chat.postMessage`.
+    Special,
+}
+
+impl Tier {
+    /// Returns the tier of a Web API method, e.g. `users.list`.
+    /// Query-parameters are ignored, and unknown methods are considered [`Tier::Tier3`].
+    #[must_use]
+    pub fn of(method: &str) -> Self {
+        let method = method.split('?').next().unwrap_or_default();
+
+        match method {
+            "admin.analytics.getFile"
+            | "admin.users.list"
+            | "search.all"
+            | "search.files"
+            | "search.messages" => Self::Tier1,
+            "apps.connections.open"
+            | "conversations.create"
+            | "conversations.list"
+            | "files.upload"
+            | "users.list"
+            | "users.lookupByEmail"
+            | "views.publish" => Self::Tier2,
+            "chat.postMessage" | "chat.postEphemeral" => Self::Special,
+            "users.info" | "views.open" | "views.push" | "views.update" => Self::Tier4,
+            _ => Self::Tier3,
+        }
+    }
+
+    /// Returns the interval between requests allowed by the tier.
+    #[must_use]
+    pub const fn interval(self) -> Duration {
+        match self {
+            Self::Tier1 => Duration::from_secs(60),
+            Self::Tier2 => Duration::from_secs(3),
+            Self::Tier3 => Duration::from_millis(1200),
+            Self::Tier4 => Duration::from_millis(600),
+            Self::Special => Duration::from_secs(1),
+        }
+    }
+}
+
+/// A policy for retrying requests to the Web API.
+///
+/// Rate-limited requests (HTTP 429) are retried after the `Retry-After` header, or the interval of the
+/// methods [`Tier`]. Requests that couldn't connect are retried with exponential backoff and jitter.
+///
+/// Server errors (HTTP 5xx) and timeouts are only retried when enabled, as slack might already have
+/// handled the request, e.g. posting a message twice if `chat.postMessage` is retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    server_errors: bool,
+    timeouts: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(60),
+            server_errors: false,
+            timeouts: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates the default retry-policy, sending each request at most 3 times.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy which never retries requests.
+    #[must_use]
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Sets the maximum amount of times a request is sent, including the first attempt.
+    #[must_use]
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry, which is doubled for each following retry.
+    #[must_use]
+    pub const fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Sets the maximum delay between attempts.
+    #[must_use]
+    pub const fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets if server errors (HTTP 5xx) are retried with exponential backoff.
+    #[must_use]
+    pub const fn server_errors(mut self, retry: bool) -> Self {
+        self.server_errors = retry;
+        self
+    }
+
+    /// Sets if timed out requests are retried with exponential backoff.
+    #[must_use]
+    pub const fn timeouts(mut self, retry: bool) -> Self {
+        self.timeouts = retry;
+        self
+    }
+
+    /// Retries server errors and timeouts as well, for requests that are safe to send more than once,
+    /// e.g. methods only reading data.
+    #[must_use]
+    pub const fn idempotent(self) -> Self {
+        self.server_errors(true).timeouts(true)
+    }
+
+    /// Returns true if server errors (HTTP 5xx) are retried.
+    #[must_use]
+    pub const fn get_server_errors(&self) -> bool {
+        self.server_errors
+    }
+
+    /// Returns true if timed out requests are retried.
+    #[must_use]
+    pub const fn get_timeouts(&self) -> bool {
+        self.timeouts
+    }
+
+    /// Returns the maximum amount of times a request is sent.
+    #[must_use]
+    pub const fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay before retrying after a failed attempt, using exponential backoff with jitter.
+    ///
+    /// The delay is a random duration between half and all of the base-delay doubled for each attempt.
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+
+        // Random jitter between 0 and 1
+        #[allow(clippy::cast_precision_loss)]
+        let jitter = (RandomState::new().hash_one(attempt) % 1000) as f64 / 1000.0;
+
+        delay.mul_f64(0.5 + jitter / 2.0)
+    }
+
+    /// Returns the delay before retrying a rate-limited request.
+    #[must_use]
+    pub fn rate_limit_delay(&self, retry_after: Option<Duration>, tier: Tier) -> Duration {
+        retry_after
+            .unwrap_or_else(|| tier.interval())
+            .min(self.max_delay)
+    }
+}
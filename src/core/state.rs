@@ -1,4 +1,8 @@
-use super::{BoltResult, Deserialize, Error, Serialize};
+use super::{
+    comp::option, file::File, json, skip_serializing_none, BoltResult, Deserialize, Error,
+    Serialize,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use std::collections::HashMap;
 
 /// A state-object from Slack.
@@ -8,34 +12,219 @@ pub struct State {
 }
 
 /// A value from a [State] object
+///
+/// Only the fields matching the type of the input-element are set.
+#[skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct Value {
     pub r#type: String,
 
+    /// The value of text, number, email and url inputs, or the selection of single-selects for
+    /// users, conversations and channels.
     #[serde(alias = "selected_conversation")]
     #[serde(alias = "selected_user")]
+    #[serde(alias = "selected_channel")]
     pub value: Option<String>,
+
+    /// The selected option of static- and external-selects, overflow-menus and radio-buttons.
+    pub selected_option: Option<option::Object>,
+
+    /// The selected options of checkboxes and multi-selects.
+    pub selected_options: Option<Vec<option::Object>>,
+
+    /// The selected date of date-pickers, formatted as `YYYY-MM-DD`.
+    pub selected_date: Option<String>,
+
+    /// The selected time of time-pickers, formatted as `HH:mm`.
+    pub selected_time: Option<String>,
+
+    /// The timezone of the selected time of time-pickers.
+    pub timezone: Option<String>,
+
+    /// The selected date and time of datetime-pickers, as a unix-timestamp.
+    pub selected_date_time: Option<i64>,
+
+    /// The selected users of multi-user-selects.
+    pub selected_users: Option<Vec<String>>,
+
+    /// The selected conversations of multi-conversation-selects.
+    pub selected_conversations: Option<Vec<String>>,
+
+    /// The selected channels of multi-channel-selects.
+    pub selected_channels: Option<Vec<String>>,
+
+    /// The uploaded files of file-inputs.
+    pub files: Option<Vec<File>>,
+
+    /// The value of rich-text inputs, as a rich-text block.
+    pub rich_text_value: Option<json::Value>,
 }
 
 impl State {
-    /// Tries to extract the value from a specified state-object.
+    /// Tries to get the state of an element within a block.
     ///
     /// # Errors
     ///
     /// Errors will occur if the requested state is not found.
-    pub fn get_value(&self, block_id: &str, action_id: &str) -> BoltResult<&String> {
+    pub fn get(&self, block_id: &str, action_id: &str) -> BoltResult<&Value> {
         let Some(block) = self.values.get(block_id) else {
-            return Err(Error::State(format!("Couldn't get state value of block: '{block_id}'")))
+            return Err(Error::State(format!(
+                "Couldn't get state value of block: '{block_id}'"
+            )));
         };
 
-        if let Some(value) = block.get(action_id) {
-            if let Some(v) = &value.value {
-                return Ok(v);
-            }
-        }
+        block.get(action_id).ok_or_else(|| {
+            Error::State(format!(
+                "Couldn't get state value of block: '{block_id}' action: '{action_id}'"
+            ))
+        })
+    }
+
+    /// Tries to extract a field from the state of an element.
+    fn field<'a, T: ?Sized>(
+        &'a self,
+        block_id: &str,
+        action_id: &str,
+        field: impl FnOnce(&'a Value) -> Option<&'a T>,
+    ) -> BoltResult<&'a T> {
+        let value = self.get(block_id, action_id)?;
+
+        field(value).ok_or_else(|| {
+            Error::State(format!(
+                "State of block: '{block_id}' action: '{action_id}' has no such value (type: '{}')",
+                value.r#type
+            ))
+        })
+    }
+
+    /// Tries to extract the value from a specified state-object.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found.
+    pub fn get_value(&self, block_id: &str, action_id: &str) -> BoltResult<&String> {
+        self.field(block_id, action_id, |value| value.value.as_ref())
+    }
+
+    /// Tries to extract the value of a number-input as a number.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or isn't a number.
+    pub fn get_number(&self, block_id: &str, action_id: &str) -> BoltResult<f64> {
+        let value = self.get_value(block_id, action_id)?;
+
+        value.parse().map_err(|_| {
+            Error::State(format!(
+                "State of block: '{block_id}' action: '{action_id}' isn't a number: '{value}'"
+            ))
+        })
+    }
+
+    /// Tries to extract the selected option of a single-select or radio-buttons.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or has no selected option.
+    pub fn get_option(&self, block_id: &str, action_id: &str) -> BoltResult<&option::Object> {
+        self.field(block_id, action_id, |value| value.selected_option.as_ref())
+    }
+
+    /// Tries to extract the selected options of checkboxes or a multi-select.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or has no selected options.
+    pub fn get_options(&self, block_id: &str, action_id: &str) -> BoltResult<&[option::Object]> {
+        self.field(block_id, action_id, |value| {
+            value.selected_options.as_deref()
+        })
+    }
+
+    /// Tries to extract the selected date of a date-picker.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or isn't a valid date.
+    pub fn get_date(&self, block_id: &str, action_id: &str) -> BoltResult<NaiveDate> {
+        let date = self.field(block_id, action_id, |value| value.selected_date.as_deref())?;
+
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|error| Error::State(format!("Couldn't parse date '{date}': {error}")))
+    }
 
-        Err(Error::State(format!(
-            "Couldn't get state value of block: '{block_id}' action: '{action_id}'"
-        )))
+    /// Tries to extract the selected time of a time-picker.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or isn't a valid time.
+    pub fn get_time(&self, block_id: &str, action_id: &str) -> BoltResult<NaiveTime> {
+        let time = self.field(block_id, action_id, |value| value.selected_time.as_deref())?;
+
+        NaiveTime::parse_from_str(time, "%H:%M")
+            .map_err(|error| Error::State(format!("Couldn't parse time '{time}': {error}")))
+    }
+
+    /// Tries to extract the selected date and time of a datetime-picker.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or isn't a valid timestamp.
+    pub fn get_date_time(&self, block_id: &str, action_id: &str) -> BoltResult<DateTime<Utc>> {
+        let timestamp = self.field(block_id, action_id, |value| {
+            value.selected_date_time.as_ref()
+        })?;
+
+        DateTime::from_timestamp(*timestamp, 0)
+            .ok_or_else(|| Error::State(format!("Couldn't parse timestamp '{timestamp}'")))
+    }
+
+    /// Tries to extract the selected users of a multi-user-select.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or has no selected users.
+    pub fn get_users(&self, block_id: &str, action_id: &str) -> BoltResult<&[String]> {
+        self.field(block_id, action_id, |value| value.selected_users.as_deref())
+    }
+
+    /// Tries to extract the selected conversations of a multi-conversation-select.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or has no selected conversations.
+    pub fn get_conversations(&self, block_id: &str, action_id: &str) -> BoltResult<&[String]> {
+        self.field(block_id, action_id, |value| {
+            value.selected_conversations.as_deref()
+        })
+    }
+
+    /// Tries to extract the selected channels of a multi-channel-select.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or has no selected channels.
+    pub fn get_channels(&self, block_id: &str, action_id: &str) -> BoltResult<&[String]> {
+        self.field(block_id, action_id, |value| {
+            value.selected_channels.as_deref()
+        })
+    }
+
+    /// Tries to extract the uploaded files of a file-input.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or has no files.
+    pub fn get_files(&self, block_id: &str, action_id: &str) -> BoltResult<&[File]> {
+        self.field(block_id, action_id, |value| value.files.as_deref())
+    }
+
+    /// Tries to extract the value of a rich-text input, as a rich-text block.
+    ///
+    /// # Errors
+    ///
+    /// Errors will occur if the requested state is not found, or has no rich-text.
+    pub fn get_rich_text(&self, block_id: &str, action_id: &str) -> BoltResult<&json::Value> {
+        self.field(block_id, action_id, |value| value.rich_text_value.as_ref())
     }
 }
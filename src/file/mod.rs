@@ -1,10 +1,11 @@
-use crate::pre::{comp, BoltResult, Deserialize, Error, Request, Serialize};
+use crate::pre::{comp, BoltResult, Deserialize, Error, Serialize, WebClient};
 use comp::Text;
 use reqwest::multipart::{Form, Part};
 use std::{fs, marker::PhantomData};
 
 /// A [File] originating from Slack
-#[derive(Deserialize, Serialize, Debug, Default)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default)]
 pub struct File {
     id: String,
     name: String,
@@ -54,33 +55,33 @@ impl File {
     /// Uses alreaady known URL's to construct the public url to send as a message in slack.
     /// This URL will **only** work if the file is made public in slack. This can be done by using the
     /// `publish` method.
-    #[must_use]
-    pub fn get_public_url(&self) -> String {
-        let filename = self.name().to_lowercase();
-        let url = url::Url::parse(self.permalink_public()).expect("Public permalink unparseable!?");
-        let segments: Vec<&str> = url
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the public permalink isn't formatted as `https://slack-files.com/{team}-{file}-{secret}`.
+    pub fn get_public_url(&self) -> BoltResult<String> {
+        let permalink = self.permalink_public();
+        let error = |error: &str| Error::File(format!("{error}: '{permalink}'"));
+
+        let url = url::Url::parse(permalink)
+            .map_err(|e| error(&format!("Couldn't parse public permalink ({e})")))?;
+        let segment = url
             .path_segments()
-            .expect("No path segments on public permalink?!")
-            .next()
-            .expect("Only one segment on public permalink?!")
-            .split('-')
-            .collect();
-
-        let (team_id, file_id, pub_secret) = (
-            // Suppress clippy warning for using "get(0)" to get the first element
-            #[allow(clippy::get_first)]
-            segments
-                .get(0)
-                .expect("Failed to extract team-id from public permalink"),
-            segments
-                .get(1)
-                .expect("Failed to extract file-id from public permalink"),
-            segments
-                .get(2)
-                .expect("Failed to extract pub-secret from public permalink"),
-        );
-
-        format!("https://files.slack.com/files-pri/{team_id}-{file_id}/{filename}?pub_secret={pub_secret}")
+            .and_then(|mut segments| segments.next())
+            .filter(|segment| !segment.is_empty())
+            .ok_or_else(|| error("No path on public permalink"))?;
+
+        let mut parts = segment.split('-').filter(|part| !part.is_empty());
+        let (Some(team_id), Some(file_id), Some(pub_secret)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(error(
+                "Couldn't extract team-id, file-id and pub-secret from public permalink",
+            ));
+        };
+
+        let filename = self.name().to_lowercase();
+        Ok(format!("https://files.slack.com/files-pri/{team_id}-{file_id}/{filename}?pub_secret={pub_secret}"))
     }
 
     /// Publishes the file for "public" consumption in slack.
@@ -92,12 +93,13 @@ impl File {
     ///
     /// An error will occur if the request fails to be sent.
     ///
-    pub async fn publish(self, token: &str) -> BoltResult<Self> {
+    pub async fn publish(self, client: &WebClient) -> BoltResult<Self> {
         let form = Form::new()
-            .text("token", token.to_owned())
+            .text("token", client.token().to_owned())
             .text("file", self.id);
 
-        Request::post("files.sharedPublicURL", token)
+        client
+            .post("files.sharedPublicURL")
             .multipart(form)
             .send()
             .await?
@@ -206,14 +208,14 @@ impl<C: Send + Sync> Upload<C> {
         self
     }
 
-        /// Uploads the content to slack, returning the file-object.
+    /// Uploads the content to slack, returning the file-object.
     ///
     /// # Errors
     ///
     /// An error will occur if the request can't be sent
     ///
-    pub async fn upload(self, token: &str) -> BoltResult<File> {
-        let mut form = Form::new().text("token", token.to_owned());
+    pub async fn upload(self, client: &WebClient) -> BoltResult<File> {
+        let mut form = Form::new().text("token", client.token().to_owned());
 
         if let Some(channels) = self.channels {
             form = form.text("channels", channels);
@@ -240,7 +242,8 @@ impl<C: Send + Sync> Upload<C> {
             form = form.text("title", title);
         }
 
-        Request::post("files.upload", token)
+        client
+            .post("files.upload")
             .multipart(form)
             .send::<File>()
             .await?
@@ -47,10 +47,6 @@ pub trait AsBlock<B: Block> {
     fn as_block(&self) -> BoltResult<B>;
 }
 
-trait ModalBlock {}
-trait MessagesBlock {}
-trait HometabBlock {}
-
 /// A list of blocks as json-strings.
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Blocks(Vec<json::Value>);
@@ -37,6 +37,19 @@ impl<T: Default + parsing::SerializeDefaultPhantomData> Object<T> {
         self
     }
 }
+impl<T: parsing::SerializeDefaultPhantomData> Object<T> {
+    /// Returns the text of the option
+    #[must_use]
+    pub const fn get_text(&self) -> &Text<T> {
+        &self.text
+    }
+
+    /// Returns the value of the option
+    #[must_use]
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+}
 
 #[skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
@@ -51,3 +64,24 @@ impl Group {
         Self { label, options }
     }
 }
+
+/// A list of options or option-groups, used when responding to
+/// option-requests from slack for elements with external-data.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum List<T: parsing::SerializeDefaultPhantomData = Plain> {
+    #[serde(rename = "options")]
+    Options(Vec<Object<T>>),
+
+    #[serde(rename = "option_groups")]
+    Groups(Vec<Group>),
+}
+impl<T: parsing::SerializeDefaultPhantomData> From<Vec<Object<T>>> for List<T> {
+    fn from(options: Vec<Object<T>>) -> Self {
+        Self::Options(options)
+    }
+}
+impl<T: parsing::SerializeDefaultPhantomData> From<Vec<Group>> for List<T> {
+    fn from(groups: Vec<Group>) -> Self {
+        Self::Groups(groups)
+    }
+}
@@ -19,4 +19,13 @@ pub use app::App;
 
 pub use layout::{block, comp as composition, element};
 
-pub use crate::core::{payload, request::Request, response::Response, BoltResult, Error};
+pub use crate::core::{
+    api_error::{ApiError, SlackApiError},
+    client::WebClient,
+    paginator::{collect_all, Paginator},
+    payload,
+    request::Request,
+    response::Response,
+    retry::{RetryPolicy, Tier},
+    BoltResult, Error,
+};
@@ -0,0 +1,277 @@
+use crate::pre::{
+    block::Blocks, parsing, skip_serializing_none, BoltResult, Deserialize, Error, Serialize,
+};
+
+/// The color of the bar along the left side of an [`Attachment`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub enum Color {
+    /// Green.
+    Good,
+
+    /// Yellow.
+    Warning,
+
+    /// Red.
+    Danger,
+
+    /// A hex color-code, e.g. `#36a64f`.
+    Hex(HexColor),
+
+    /// Any other color received from slack, e.g. a 3-digit hex color-code from a legacy integration.
+    Other(String),
+}
+
+/// A validated hex color-code, e.g. `#36a64f`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexColor(String);
+
+impl HexColor {
+    /// Creates a hex color-code, e.g. from `#36a64f`.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the code doesn't consist of 6 hexadecimal digits, with an optional leading `#`.
+    ///
+    pub fn new(code: &str) -> BoltResult<Self> {
+        let digits = code.strip_prefix('#').unwrap_or(code);
+
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::Message(format!("Invalid hex color-code: '{code}'")));
+        }
+
+        Ok(Self(format!("#{}", digits.to_lowercase())))
+    }
+
+    /// Returns the color-code, in lowercase with a leading `#`.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Color {
+    /// Creates a color from a hex color-code, e.g. `#36a64f`.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the code doesn't consist of 6 hexadecimal digits, with an optional leading `#`.
+    ///
+    pub fn hex(code: &str) -> BoltResult<Self> {
+        HexColor::new(code).map(Self::Hex)
+    }
+
+    /// Creates a color from its red, green and blue components.
+    #[must_use]
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::Hex(HexColor(format!("#{red:02x}{green:02x}{blue:02x}")))
+    }
+}
+
+impl From<String> for Color {
+    fn from(color: String) -> Self {
+        match color.as_str() {
+            "good" => Self::Good,
+            "warning" => Self::Warning,
+            "danger" => Self::Danger,
+            code => Self::hex(code).unwrap_or(Self::Other(color)),
+        }
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Good => "good".to_string(),
+            Color::Warning => "warning".to_string(),
+            Color::Danger => "danger".to_string(),
+            Color::Hex(HexColor(code)) | Color::Other(code) => code,
+        }
+    }
+}
+
+/// A field of an [`Attachment`], shown in a table below the text.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct AttachmentField {
+    /// The bold heading of the field.
+    #[serde(default)]
+    pub title: String,
+
+    /// The value of the field, which may contain markdown.
+    #[serde(default)]
+    pub value: String,
+
+    /// If the field is short enough to be shown side-by-side with other fields.
+    #[serde(default)]
+    pub short: bool,
+}
+
+/// A legacy (secondary) attachment, shown below the content of a message with a colored bar.
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Attachment {
+    fallback: Option<String>,
+    color: Option<Color>,
+    pretext: Option<String>,
+    author_name: Option<String>,
+    author_link: Option<String>,
+    author_icon: Option<String>,
+    title: Option<String>,
+    title_link: Option<String>,
+    text: Option<String>,
+    fields: Option<Vec<AttachmentField>>,
+    image_url: Option<String>,
+    thumb_url: Option<String>,
+    footer: Option<String>,
+    footer_icon: Option<String>,
+    #[serde(default, deserialize_with = "parsing::number_or_string")]
+    ts: Option<f64>,
+    mrkdwn_in: Option<Vec<String>>,
+    blocks: Option<Blocks>,
+}
+
+impl Attachment {
+    /// Creates a new, empty attachment
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the plain-text summary, shown in notifications and clients without attachment-support.
+    #[must_use]
+    pub fn fallback(mut self, fallback: &str) -> Self {
+        self.fallback = Some(fallback.to_string());
+        self
+    }
+
+    /// Sets the color of the bar along the left side.
+    #[must_use]
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets the text shown above the attachment.
+    #[must_use]
+    pub fn pretext(mut self, pretext: &str) -> Self {
+        self.pretext = Some(pretext.to_string());
+        self
+    }
+
+    /// Sets the author, shown in small text at the top.
+    #[must_use]
+    pub fn author(mut self, name: &str) -> Self {
+        self.author_name = Some(name.to_string());
+        self
+    }
+
+    /// Makes the author a link.
+    #[must_use]
+    pub fn author_link(mut self, link: &str) -> Self {
+        self.author_link = Some(link.to_string());
+        self
+    }
+
+    /// Sets the url of an icon shown next to the author.
+    #[must_use]
+    pub fn author_icon(mut self, icon_url: &str) -> Self {
+        self.author_icon = Some(icon_url.to_string());
+        self
+    }
+
+    /// Sets the title, shown in bold.
+    #[must_use]
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Makes the title a link.
+    #[must_use]
+    pub fn title_link(mut self, link: &str) -> Self {
+        self.title_link = Some(link.to_string());
+        self
+    }
+
+    /// Sets the main text.
+    #[must_use]
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Adds a field to the table below the text.
+    #[must_use]
+    pub fn field(mut self, title: &str, value: &str, short: bool) -> Self {
+        self.fields
+            .get_or_insert_with(Vec::new)
+            .push(AttachmentField {
+                title: title.to_string(),
+                value: value.to_string(),
+                short,
+            });
+        self
+    }
+
+    /// Sets the url of an image shown below the fields.
+    #[must_use]
+    pub fn image_url(mut self, image_url: &str) -> Self {
+        self.image_url = Some(image_url.to_string());
+        self
+    }
+
+    /// Sets the url of a thumbnail shown to the right.
+    #[must_use]
+    pub fn thumb_url(mut self, thumb_url: &str) -> Self {
+        self.thumb_url = Some(thumb_url.to_string());
+        self
+    }
+
+    /// Sets the text shown in small text at the bottom.
+    #[must_use]
+    pub fn footer(mut self, footer: &str) -> Self {
+        self.footer = Some(footer.to_string());
+        self
+    }
+
+    /// Sets the url of an icon shown next to the footer.
+    #[must_use]
+    pub fn footer_icon(mut self, icon_url: &str) -> Self {
+        self.footer_icon = Some(icon_url.to_string());
+        self
+    }
+
+    /// Sets the unix-timestamp shown next to the footer, optionally with fractional seconds.
+    #[must_use]
+    pub const fn ts(mut self, ts: f64) -> Self {
+        self.ts = Some(ts);
+        self
+    }
+
+    /// Sets the fields formatted as markdown, e.g. `["text", "pretext", "fields"]`.
+    #[must_use]
+    pub fn mrkdwn_in(mut self, fields: &[&str]) -> Self {
+        self.mrkdwn_in = Some(fields.iter().map(ToString::to_string).collect());
+        self
+    }
+
+    /// Adds blocks to the attachment, shown instead of the legacy fields.
+    #[must_use]
+    pub fn blocks(mut self, blocks: Blocks) -> Self {
+        self.blocks = Some(blocks);
+        self
+    }
+
+    /// Returns the color of the attachment, if any.
+    #[must_use]
+    pub const fn get_color(&self) -> Option<&Color> {
+        self.color.as_ref()
+    }
+
+    /// Returns the fields of the attachment.
+    #[must_use]
+    pub fn get_fields(&self) -> &[AttachmentField] {
+        self.fields.as_deref().unwrap_or_default()
+    }
+}
@@ -1,7 +1,12 @@
 use crate::pre::{
-    block::Blocks, element::Elements, skip_serializing_none, BoltResult, Deserialize, Request,
-    Serialize,
+    block::Blocks, json, skip_serializing_none, BoltResult, Deserialize, Error, Paginator,
+    Response, Serialize, WebClient,
 };
+use futures::stream::BoxStream;
+
+mod attachment;
+
+pub use attachment::{Attachment, AttachmentField, Color, HexColor};
 
 /// Convert any type into a message
 #[allow(clippy::module_name_repetitions)]
@@ -20,8 +25,55 @@ pub trait AsMessage {
 pub struct Message {
     channel: String,
     blocks: Option<Blocks>,
-    attachments: Option<Elements>,
+    attachments: Option<Vec<Attachment>>,
     text: Option<String>,
+    thread_ts: Option<String>,
+    reply_broadcast: Option<bool>,
+    unfurl_links: Option<bool>,
+    unfurl_media: Option<bool>,
+    mrkdwn: Option<bool>,
+    parse: Option<Parse>,
+    link_names: Option<bool>,
+    username: Option<String>,
+    icon_emoji: Option<String>,
+    icon_url: Option<String>,
+    as_user: Option<bool>,
+    metadata: Option<json::Value>,
+    #[serde(skip)]
+    metadata_error: Option<json::Error>,
+}
+
+/// How slack should parse the text of a [`Message`].
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Parse {
+    /// Links, channels and users are linked automatically.
+    Full,
+
+    /// The text is only parsed as markdown, which is the default.
+    None,
+}
+
+/// Metadata attached to a [`Message`], describing an event with a typed payload.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct MessageMetadata<T> {
+    /// The type of the event, e.g. `task_created`.
+    pub event_type: String,
+
+    /// The payload of the event.
+    pub event_payload: T,
+}
+
+impl<T: Serialize> MessageMetadata<T> {
+    /// Creates new metadata for an event.
+    #[must_use]
+    pub fn new(event_type: &str, event_payload: T) -> Self {
+        Self {
+            event_type: event_type.to_string(),
+            event_payload,
+        }
+    }
 }
 
 impl Message {
@@ -52,24 +104,505 @@ impl Message {
         self
     }
 
-    /// Adds attachments to the message
+    /// Adds legacy attachments to the message
     #[must_use]
-    pub fn attachments(mut self, attachments: Elements) -> Self {
+    pub fn attachments(mut self, attachments: Vec<Attachment>) -> Self {
         self.attachments = Some(attachments);
         self
     }
 
-    /// Posts the message to slack.
+    /// Posts the message as a reply in the thread of a message, using the timestamp of the parent message.
+    #[must_use]
+    pub fn thread_ts(mut self, thread_ts: &str) -> Self {
+        self.thread_ts = Some(thread_ts.to_string());
+        self
+    }
+
+    /// Makes a reply in a thread visible to everyone in the channel.
+    #[must_use]
+    pub const fn reply_broadcast(mut self) -> Self {
+        self.reply_broadcast = Some(true);
+        self
+    }
+
+    /// Sets whether text-based content should be unfurled.
+    #[must_use]
+    pub const fn unfurl_links(mut self, unfurl_links: bool) -> Self {
+        self.unfurl_links = Some(unfurl_links);
+        self
+    }
+
+    /// Sets whether media-content should be unfurled.
+    #[must_use]
+    pub const fn unfurl_media(mut self, unfurl_media: bool) -> Self {
+        self.unfurl_media = Some(unfurl_media);
+        self
+    }
+
+    /// Sets whether the text should be formatted as markdown.
+    /// Default is `true`
+    #[must_use]
+    pub const fn mrkdwn(mut self, mrkdwn: bool) -> Self {
+        self.mrkdwn = Some(mrkdwn);
+        self
+    }
+
+    /// Sets how the text should be parsed.
+    #[must_use]
+    pub const fn parse(mut self, parse: Parse) -> Self {
+        self.parse = Some(parse);
+        self
+    }
+
+    /// Sets whether channel-names and usernames should be linked.
+    #[must_use]
+    pub const fn link_names(mut self, link_names: bool) -> Self {
+        self.link_names = Some(link_names);
+        self
+    }
+
+    /// Overrides the username of the bot for the message.
+    ///
+    /// **Disclaimer**: This requires the `chat:write.customize` scope.
+    #[must_use]
+    pub fn username(mut self, username: &str) -> Self {
+        self.username = Some(username.to_string());
+        self
+    }
+
+    /// Overrides the icon of the bot for the message with an emoji, e.g. `:chart_with_upwards_trend:`.
+    ///
+    /// **Disclaimer**: This requires the `chat:write.customize` scope.
+    #[must_use]
+    pub fn icon_emoji(mut self, icon_emoji: &str) -> Self {
+        self.icon_emoji = Some(icon_emoji.to_string());
+        self
+    }
+
+    /// Overrides the icon of the bot for the message with an image.
+    ///
+    /// **Disclaimer**: This requires the `chat:write.customize` scope.
+    #[must_use]
+    pub fn icon_url(mut self, icon_url: &str) -> Self {
+        self.icon_url = Some(icon_url.to_string());
+        self
+    }
+
+    /// Posts the message as the authed user, instead of as a bot.
+    ///
+    /// **Disclaimer**: This is only supported by legacy apps.
+    #[must_use]
+    pub const fn as_user(mut self, as_user: bool) -> Self {
+        self.as_user = Some(as_user);
+        self
+    }
+
+    /// Attaches metadata to the message.
+    ///
+    /// If the payload of the metadata fails to serialize, sending the message fails.
+    #[must_use]
+    pub fn metadata<T: Serialize>(mut self, metadata: &MessageMetadata<T>) -> Self {
+        match json::to_value(metadata) {
+            Ok(metadata) => {
+                self.metadata = Some(metadata);
+                self.metadata_error = None;
+            }
+            Err(error) => self.metadata_error = Some(error),
+        }
+        self
+    }
+
+    /// Posts the message to slack, returning a handle to the posted message.
     ///
     /// # Errors
     ///
     /// An error occurs if the request fails to be sent, or if slack reports any errors back.
     ///
-    pub async fn post(self, token: &str) -> BoltResult<Self> {
-        Request::post("chat.postMessage", token)
-            .json(&self)
-            .send()
+    pub async fn post(self, client: &WebClient) -> BoltResult<PostedMessage> {
+        let response = client
+            .post("chat.postMessage")
+            .json(&self.payload([])?)
+            .send::<json::Value>()
+            .await?;
+
+        PostedMessage::from_response(&response, &self.channel)
+    }
+
+    /// Posts the message as an ephemeral message, only visible to the user.
+    /// Returns the timestamp of the message.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn post_ephemeral(self, user: &str, client: &WebClient) -> BoltResult<String> {
+        let response = client
+            .post("chat.postEphemeral")
+            .json(&self.payload([("user", json::Value::from(user))])?)
+            .send::<json::Value>()
+            .await?;
+
+        response.confirm()?;
+        response
+            .message_ts()
+            .map(ToString::to_string)
+            .ok_or_else(|| Error::Message("No timestamp on ephemeral message?!".to_string()))
+    }
+
+    /// Schedules the message to be posted at a unix-timestamp.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack reports any errors back,
+    /// e.g. if the timestamp is in the past or more than 120 days in the future.
+    ///
+    pub async fn schedule(self, post_at: i64, client: &WebClient) -> BoltResult<ScheduledMessage> {
+        let response = client
+            .post("chat.scheduleMessage")
+            .json(&self.payload([("post_at", json::Value::from(post_at))])?)
+            .send::<json::Value>()
+            .await?;
+
+        response.confirm()?;
+        Ok(ScheduledMessage {
+            id: response
+                .scheduled_message_id()
+                .ok_or_else(|| Error::Message("No id on scheduled message?!".to_string()))?
+                .to_string(),
+            channel_id: self.channel,
+            post_at: response.post_at().unwrap_or(post_at),
+            date_created: None,
+            text: self.text,
+        })
+    }
+
+    /// Posts the text of the message as a "me message", shown in italics.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn me_message(self, client: &WebClient) -> BoltResult<PostedMessage> {
+        let response = client
+            .post("chat.meMessage")
+            .json(&json::json!({ "channel": self.channel, "text": self.text }))
+            .send::<json::Value>()
+            .await?;
+
+        PostedMessage::from_response(&response, &self.channel)
+    }
+
+    /// Serializes the message, with additional fields for the request.
+    fn payload<const N: usize>(&self, fields: [(&str, json::Value); N]) -> BoltResult<json::Value> {
+        if let Some(error) = &self.metadata_error {
+            return Err(Error::Message(format!(
+                "Couldn't serialize the metadata of the message: {error}"
+            )));
+        }
+
+        let mut payload =
+            json::to_value(self).map_err(|error| Error::Building("Message".to_string(), error))?;
+
+        if let Some(object) = payload.as_object_mut() {
+            for (key, value) in fields {
+                object.insert(key.to_string(), value);
+            }
+        }
+
+        Ok(payload)
+    }
+}
+
+/// A handle to a message posted in a channel, identified by the channel and the timestamp of the message.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PostedMessage {
+    channel: String,
+    ts: String,
+}
+
+impl PostedMessage {
+    /// Creates a handle to a message, e.g. from the channel and timestamp of an event.
+    #[must_use]
+    pub fn new(channel: &str, ts: &str) -> Self {
+        Self {
+            channel: channel.to_string(),
+            ts: ts.to_string(),
+        }
+    }
+
+    /// Returns the id of the channel the message was posted in.
+    #[must_use]
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// Returns the timestamp of the message, which identifies it within the channel.
+    #[must_use]
+    pub fn ts(&self) -> &str {
+        &self.ts
+    }
+
+    /// Creates a handle from the response of a posted message.
+    fn from_response(response: &Response<json::Value>, channel: &str) -> BoltResult<Self> {
+        response.confirm()?;
+
+        let Some(ts) = response.ts() else {
+            return Err(Error::Message(
+                "No timestamp on posted message?!".to_string(),
+            ));
+        };
+
+        // The channel is moved into the message of the response, or kept as the value
+        let channel = response
+            .value()
+            .and_then(|value| value["channel"].as_str().or_else(|| value.as_str()))
+            .unwrap_or(channel);
+
+        Ok(Self::new(channel, ts))
+    }
+
+    /// Replaces the contents of the message.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn update(&self, message: Message, client: &WebClient) -> BoltResult<Self> {
+        let response = client
+            .post("chat.update")
+            .json(
+                &message
+                    .channel(&self.channel)
+                    .payload([("ts", json::Value::from(self.ts.as_str()))])?,
+            )
+            .send::<json::Value>()
+            .await?;
+
+        Self::from_response(&response, &self.channel)
+    }
+
+    /// Deletes the message.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn delete(&self, client: &WebClient) -> BoltResult<()> {
+        client
+            .post("chat.delete")
+            .json(&json::json!({ "channel": self.channel, "ts": self.ts }))
+            .send::<json::Value>()
             .await?
-            .unpack()
+            .confirm()
+    }
+
+    /// Posts a message as a reply in the thread of the message.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn reply_in_thread(&self, message: Message, client: &WebClient) -> BoltResult<Self> {
+        message
+            .channel(&self.channel)
+            .thread_ts(&self.ts)
+            .post(client)
+            .await
+    }
+
+    /// Adds a reaction to the message, using the name of an emoji without colons, e.g. `thumbsup`.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn react(&self, name: &str, client: &WebClient) -> BoltResult<()> {
+        client
+            .post("reactions.add")
+            .json(&json::json!({ "channel": self.channel, "timestamp": self.ts, "name": name }))
+            .send::<json::Value>()
+            .await?
+            .confirm()
+    }
+
+    /// Returns a permanent url to the message.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn permalink(&self, client: &WebClient) -> BoltResult<String> {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("channel", &self.channel)
+            .append_pair("message_ts", &self.ts)
+            .finish();
+
+        let response = client
+            .get(&format!("chat.getPermalink?{query}"))
+            .send::<json::Value>()
+            .await?;
+
+        response.confirm()?;
+        response
+            .permalink()
+            .map(ToString::to_string)
+            .ok_or_else(|| Error::Message("No permalink on response?!".to_string()))
+    }
+}
+
+/// A message scheduled to be posted in a channel.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ScheduledMessage {
+    /// The id of the scheduled message.
+    pub id: String,
+
+    /// The channel the message will be posted in.
+    pub channel_id: String,
+
+    /// The unix-timestamp the message will be posted at.
+    pub post_at: i64,
+
+    /// The unix-timestamp the message was scheduled at.
+    pub date_created: Option<i64>,
+
+    /// The text of the message.
+    pub text: Option<String>,
+}
+
+impl ScheduledMessage {
+    /// Streams the messages scheduled by the app, optionally only for a channel,
+    /// requesting the pages of `chat.scheduledMessages.list` as needed.
+    #[must_use]
+    pub fn list(client: &WebClient, channel: Option<&str>) -> BoxStream<'static, BoltResult<Self>> {
+        let mut paginator = Paginator::new(client, "chat.scheduledMessages.list");
+        if let Some(channel) = channel {
+            paginator = paginator.param("channel", channel);
+        }
+
+        paginator.stream()
+    }
+
+    /// Deletes the scheduled message, before it's posted.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn delete(&self, client: &WebClient) -> BoltResult<()> {
+        client
+            .post("chat.deleteScheduledMessage")
+            .json(&json::json!({ "channel": self.channel_id, "scheduled_message_id": self.id }))
+            .send::<json::Value>()
+            .await?
+            .confirm()
+    }
+}
+
+/// Where a [`ResponseMessage`] should be shown.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseType {
+    /// Only visible to the user who interacted.
+    #[default]
+    Ephemeral,
+
+    /// Visible to everyone in the channel.
+    InChannel,
+}
+
+/// A message sent as a response to an interaction, either as the HTTP reply
+/// or to the `response_url` of the interaction.
+#[allow(clippy::module_name_repetitions)]
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct ResponseMessage {
+    response_type: Option<ResponseType>,
+    text: Option<String>,
+    blocks: Option<Blocks>,
+    replace_original: Option<bool>,
+    delete_original: Option<bool>,
+}
+
+impl ResponseMessage {
+    /// Creates a new ephemeral response-message
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the text of the response
+    #[must_use]
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Adds blocks to the response
+    #[must_use]
+    pub fn blocks(mut self, blocks: Blocks) -> Self {
+        self.blocks = Some(blocks);
+        self
+    }
+
+    /// Makes the response visible to everyone in the channel
+    #[must_use]
+    pub const fn in_channel(mut self) -> Self {
+        self.response_type = Some(ResponseType::InChannel);
+        self
+    }
+
+    /// Makes the response only visible to the user who interacted
+    #[must_use]
+    pub const fn ephemeral(mut self) -> Self {
+        self.response_type = Some(ResponseType::Ephemeral);
+        self
+    }
+
+    /// Replaces the message the interaction originated from
+    #[must_use]
+    pub const fn replace_original(mut self) -> Self {
+        self.replace_original = Some(true);
+        self
+    }
+
+    /// Deletes the message the interaction originated from
+    #[must_use]
+    pub const fn delete_original(mut self) -> Self {
+        self.delete_original = Some(true);
+        self
+    }
+
+    /// Sends the response to a `response_url`.
+    ///
+    /// # Errors
+    ///
+    /// An error occurs if the request fails to be sent, or if slack rejects the response.
+    ///
+    pub async fn send(&self, response_url: &str) -> BoltResult<()> {
+        let response = crate::core::client::shared_client()
+            .post(response_url)
+            .json(self)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Error::Message(format!(
+                "Response-url rejected the response ({}): {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Message> for ResponseMessage {
+    fn from(message: Message) -> Self {
+        Self {
+            text: message.text,
+            blocks: message.blocks,
+            ..Default::default()
+        }
     }
 }
@@ -1,10 +1,15 @@
 pub use crate::{
     core::{
-        parsing, payload, request::Request, response::Response, state, BoltResult, Build, Error,
+        client::WebClient,
+        paginator::{collect_all, Paginator},
+        parsing, payload,
+        request::Request,
+        response::Response,
+        state, BoltResult, Build, Error,
     },
     //app,
     file,
-    layout::{block, comp, element, HomeTab, Modal, ModalResponse, Style},
+    layout::{block, comp, HomeTab, Modal, ModalResponse},
     message,
     user,
     view,
@@ -3,11 +3,16 @@ mod test {
     use crate::pre::{
         block::{self, AsBlock, AsBlocks, Blocks},
         comp::Text,
-        element, json,
-        message::{AsMessage, Message},
-        BoltResult, Build,
+        file::File,
+        json,
+        message::{AsMessage, Attachment, Color, HexColor, Message, MessageMetadata, Parse},
+        state::State,
+        view::{View, ViewSubmissionResponse},
+        BoltResult, Build, Error, Modal, ModalResponse, Response,
     };
+    use crate::{element, RetryPolicy, SlackApiError, Tier};
     use json::json;
+    use std::time::Duration;
 
     #[derive(Clone)]
     pub struct TestUser {
@@ -248,4 +253,486 @@ mod test {
             vec!["Action1".to_string(), "Section1".to_string()]
         )
     }
+
+    #[test]
+    fn view_submission_responses() {
+        let errors =
+            serde_json::to_value(ViewSubmissionResponse::errors([("input_1", "Required")]))
+                .unwrap();
+
+        assert_eq!(
+            errors,
+            json!({
+                "response_action": "errors",
+                "errors": {
+                    "input_1": "Required",
+                }
+            })
+        );
+
+        let update = serde_json::to_value(ViewSubmissionResponse::update(View::modal(
+            Text::plain("Step 2"),
+            Blocks::new(),
+        )))
+        .unwrap();
+
+        assert_eq!(
+            update,
+            json!({
+                "response_action": "update",
+                "view": {
+                    "type": "modal",
+                    "title": {
+                        "type": "plain_text",
+                        "text": "Step 2",
+                        "emoji": false,
+                    },
+                    "blocks": [],
+                }
+            })
+        );
+
+        let clear = serde_json::to_value(ViewSubmissionResponse::clear()).unwrap();
+
+        assert_eq!(clear, json!({ "response_action": "clear" }))
+    }
+
+    #[test]
+    fn retry_policy() {
+        assert_eq!(Tier::of("users.list"), Tier::Tier2);
+        assert_eq!(Tier::of("users.info?user=U123"), Tier::Tier4);
+        assert_eq!(Tier::of("chat.postMessage"), Tier::Special);
+        assert_eq!(Tier::of("some.unknownMethod"), Tier::Tier3);
+
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(10));
+
+        // Exponential backoff with jitter, capped at the max-delay
+        for (attempt, max) in [(1, 1), (2, 2), (3, 4), (4, 8), (10, 10)] {
+            let delay = policy.backoff(attempt);
+            let max = Duration::from_secs(max);
+
+            assert!(delay >= max / 2 && delay <= max);
+        }
+
+        // Retry-After takes precedence over the tier
+        assert_eq!(
+            policy.rate_limit_delay(Some(Duration::from_secs(2)), Tier::Tier1),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            policy.rate_limit_delay(None, Tier::Tier1),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            policy.rate_limit_delay(None, Tier::Tier2),
+            Duration::from_secs(3)
+        );
+
+        assert_eq!(RetryPolicy::none().get_max_attempts(), 1);
+
+        // Server errors and timeouts are only retried when opted into
+        assert!(!policy.get_server_errors() && !policy.get_timeouts());
+        assert!(policy.clone().server_errors(true).get_server_errors());
+        let idempotent = policy.idempotent();
+        assert!(idempotent.get_server_errors() && idempotent.get_timeouts());
+    }
+
+    #[test]
+    fn api_errors() {
+        let response: Response<json::Value> = json::from_value(json!({
+            "ok": false,
+            "error": "invalid_blocks",
+            "warning": "missing_charset",
+            "response_metadata": {
+                "messages": ["[ERROR] must provide a string [json-pointer:/blocks/0/text]"],
+                "warnings": ["superfluous_charset"]
+            }
+        }))
+        .unwrap();
+
+        let Err(Error::Api(error)) = response.unpack() else {
+            panic!("Expected an API-error!");
+        };
+        assert_eq!(error.error, SlackApiError::InvalidBlocks);
+        assert_eq!(error.messages.len(), 1);
+        assert_eq!(error.warnings, ["superfluous_charset", "missing_charset"]);
+
+        assert_eq!(
+            SlackApiError::from("channel_not_found"),
+            SlackApiError::ChannelNotFound
+        );
+        assert_eq!(
+            SlackApiError::from("not_in_channel"),
+            SlackApiError::NotInChannel
+        );
+        assert_eq!(
+            SlackApiError::from("some_new_error"),
+            SlackApiError::Other("some_new_error".to_string())
+        );
+        assert_eq!(SlackApiError::InvalidAuth.code(), "invalid_auth");
+        assert_eq!(
+            Error::Api(error).api_error(),
+            Some(&SlackApiError::InvalidBlocks)
+        );
+    }
+
+    #[test]
+    fn malformed_response_bodies() {
+        let valid = r#"{"ok":true,"channel":"C123","message":{"text":"Hello 🦀","ts":"1.2"}}"#;
+        let long = format!("<html>{}</html>", "ä".repeat(1000));
+
+        // Bodies that must be rejected
+        let mut malformed = vec![
+            "",
+            " ",
+            "null",
+            "[]",
+            "42",
+            "\"ok\"",
+            "{",
+            r#"{"ok":"yes"}"#,
+            r#"{"ok":true,"channel":5,"message":7}"#,
+            "<html><body>502 Bad Gateway</body></html>",
+            "\u{0}\u{1}\u{fffd}",
+            long.as_str(),
+        ];
+        // Every truncation of a valid body
+        malformed.extend((0..valid.len()).filter_map(|index| valid.get(..index)));
+
+        for body in malformed {
+            let result = Response::<json::Value>::from_body(body, 502);
+            assert!(result.is_err(), "Accepted malformed body: {body:?}");
+
+            let error = result.unwrap_err();
+            assert!(matches!(error, Error::Response(..)), "{error}");
+
+            let error = error.to_string();
+            assert!(error.contains("HTTP 502"), "{error}");
+            // Long bodies are cut to a snippet
+            assert!(error.len() < 1000, "{error}");
+        }
+
+        // A value of any shape is accepted, unless it doesn't match the type of the response
+        let any = r#"{"ok":true,"message":[1,2,3]}"#;
+        assert_eq!(
+            Response::<json::Value>::from_body(any, 200)
+                .unwrap()
+                .unpack()
+                .unwrap(),
+            json!([1, 2, 3])
+        );
+        assert!(matches!(
+            Response::<String>::from_body(any, 200),
+            Err(Error::Response(..))
+        ));
+
+        let response = Response::<json::Value>::from_body(valid, 200).unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.unpack().unwrap()["channel"], "C123");
+    }
+
+    #[test]
+    fn malformed_public_permalinks() {
+        let file = |permalink: &str| -> File {
+            json::from_value(json!({
+                "id": "F123",
+                "name": "Image.PNG",
+                "url_private": "",
+                "url_private_download": "",
+                "permalink": "",
+                "permalink_public": permalink
+            }))
+            .unwrap()
+        };
+
+        for permalink in [
+            "",
+            "not a url",
+            "https://slack-files.com",
+            "https://slack-files.com/",
+            "https://slack-files.com/T123",
+            "https://slack-files.com/T123-F123",
+            "https://slack-files.com/T123--abc",
+            "mailto:someone@example.com",
+        ] {
+            assert!(file(permalink).get_public_url().is_err(), "{permalink}");
+        }
+
+        assert_eq!(
+            file("https://slack-files.com/T123-F123-abc")
+                .get_public_url()
+                .unwrap(),
+            "https://files.slack.com/files-pri/T123-F123/image.png?pub_secret=abc"
+        );
+    }
+
+    #[test]
+    fn message_options() {
+        #[derive(serde::Serialize)]
+        struct Task {
+            id: u32,
+            title: &'static str,
+        }
+
+        let message = Message::new()
+            .channel("C123")
+            .text("Task created")
+            .thread_ts("1700000000.000100")
+            .reply_broadcast()
+            .unfurl_links(false)
+            .unfurl_media(false)
+            .mrkdwn(false)
+            .parse(Parse::None)
+            .link_names(true)
+            .username("Tasks")
+            .icon_emoji(":memo:")
+            .metadata(&MessageMetadata::new(
+                "task_created",
+                Task {
+                    id: 1,
+                    title: "Write tests",
+                },
+            ))
+            .icon_url("https://example.com/icon.png");
+
+        assert_eq!(
+            json::to_value(message).unwrap(),
+            json!({
+                "channel": "C123",
+                "text": "Task created",
+                "thread_ts": "1700000000.000100",
+                "reply_broadcast": true,
+                "unfurl_links": false,
+                "unfurl_media": false,
+                "mrkdwn": false,
+                "parse": "none",
+                "link_names": true,
+                "username": "Tasks",
+                "icon_emoji": ":memo:",
+                "icon_url": "https://example.com/icon.png",
+                "metadata": {
+                    "event_type": "task_created",
+                    "event_payload": { "id": 1, "title": "Write tests" }
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn unserializable_message_metadata() {
+        // Maps with non-string keys can't be serialized to JSON, which fails before anything is sent
+        let payload = std::collections::HashMap::from([((1, 2), "point")]);
+        let message = Message::new()
+            .channel("C123")
+            .metadata(&MessageMetadata::new("points", payload))
+            .text("Points");
+
+        let result = message.post(&crate::WebClient::new("xoxb-test")).await;
+        assert!(matches!(result, Err(Error::Message(..))), "{result:?}");
+    }
+
+    #[test]
+    fn attachments() {
+        let message = Message::new().channel("C123").attachments(vec![
+            Attachment::new()
+                .fallback("Deploy failed")
+                .color(Color::Danger)
+                .title("Deploy failed")
+                .field("Service", "api", true)
+                .field("Environment", "production", true)
+                .footer("CI")
+                .ts(1_700_000_000.0),
+            Attachment::new().color(Color::hex("36A64F").unwrap()),
+            Attachment::new().color(Color::rgb(255, 0, 16)),
+        ]);
+
+        assert_eq!(
+            json::to_value(message).unwrap()["attachments"],
+            json!([
+                {
+                    "fallback": "Deploy failed",
+                    "color": "danger",
+                    "title": "Deploy failed",
+                    "fields": [
+                        { "title": "Service", "value": "api", "short": true },
+                        { "title": "Environment", "value": "production", "short": true }
+                    ],
+                    "footer": "CI",
+                    "ts": 1_700_000_000.0
+                },
+                { "color": "#36a64f" },
+                { "color": "#ff0010" }
+            ])
+        );
+
+        assert!(Color::hex("#36a64").is_err());
+        assert!(Color::hex("#36a64g").is_err());
+        assert_eq!(HexColor::new("36A64F").unwrap().as_str(), "#36a64f");
+
+        // Attachments from slack may have timestamps as strings, and colors without a leading '#'
+        let attachment: Attachment =
+            json::from_value(json!({ "id": 1, "color": "36A64F", "ts": "1700000000.000100" }))
+                .unwrap();
+        assert_eq!(
+            attachment.get_color(),
+            Some(&Color::hex("#36a64f").unwrap())
+        );
+        assert_eq!(
+            json::to_value(attachment).unwrap()["ts"],
+            1_700_000_000.000_1
+        );
+
+        // Other colors from slack are kept as they are, instead of failing the whole payload
+        let attachment: Attachment =
+            json::from_value(json!({ "color": "#abc", "ts": 1_700_000_000 })).unwrap();
+        assert_eq!(
+            attachment.get_color(),
+            Some(&Color::Other("#abc".to_string()))
+        );
+        assert_eq!(json::to_value(attachment).unwrap()["color"], "#abc");
+        assert_eq!(
+            json::from_value::<Color>(json!("good")).unwrap(),
+            Color::Good
+        );
+    }
+
+    #[test]
+    fn private_metadata() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Wizard {
+            step: u8,
+            channel: String,
+        }
+
+        let wizard = Wizard {
+            step: 2,
+            channel: "C123".to_string(),
+        };
+        // Views from slack are deserialized as responses
+        let submitted = |view: View<Modal>| -> View<ModalResponse> {
+            json::from_value(json::to_value(view).unwrap()).unwrap()
+        };
+        let modal = || View::modal(Text::plain("Wizard"), Blocks::new());
+
+        let view = submitted(modal().metadata(&wizard).unwrap());
+        assert_eq!(
+            view.get_private_metadata().unwrap(),
+            r#"{"step":2,"channel":"C123"}"#
+        );
+        assert_eq!(view.get_metadata::<Wizard>().unwrap(), wizard);
+        assert!(view.verified_metadata::<Wizard>("secret").is_err());
+
+        let view = submitted(modal().signed_metadata(&wizard, "secret").unwrap());
+        assert_eq!(view.verified_metadata::<Wizard>("secret").unwrap(), wizard);
+        assert!(view.verified_metadata::<Wizard>("other secret").is_err());
+
+        // Tampered metadata is rejected
+        let signed = view.get_private_metadata().unwrap();
+        let tampered = submitted(modal().private_metadata(&signed.replace("C123", "C999")));
+        assert!(tampered.verified_metadata::<Wizard>("secret").is_err());
+
+        // Metadata exceeding the limit is rejected
+        assert!(modal().metadata(&"a".repeat(2998)).is_ok());
+        assert!(modal().metadata(&"a".repeat(2999)).is_err());
+        assert!(modal()
+            .signed_metadata(&"a".repeat(2950), "secret")
+            .is_err());
+    }
+
+    #[test]
+    fn state_values() {
+        let state: State = json::from_value(json!({
+            "values": {
+                "form": {
+                    "name": { "type": "plain_text_input", "value": "Bolt" },
+                    "age": { "type": "number_input", "value": "4.5" },
+                    "owner": { "type": "users_select", "selected_user": "U1" },
+                    "fruit": {
+                        "type": "static_select",
+                        "selected_option": { "text": { "type": "plain_text", "text": "Apple" }, "value": "apple" }
+                    },
+                    "toppings": {
+                        "type": "checkboxes",
+                        "selected_options": [
+                            { "text": { "type": "mrkdwn", "text": "*Cheese*" }, "value": "cheese" },
+                            { "text": { "type": "mrkdwn", "text": "*Ham*" }, "value": "ham" }
+                        ]
+                    },
+                    "date": { "type": "datepicker", "selected_date": "2024-02-29" },
+                    "time": { "type": "timepicker", "selected_time": "13:37", "timezone": "Europe/Oslo" },
+                    "when": { "type": "datetimepicker", "selected_date_time": 1_700_000_000 },
+                    "users": { "type": "multi_users_select", "selected_users": ["U1", "U2"] },
+                    "convos": { "type": "multi_conversations_select", "selected_conversations": ["C1"] },
+                    "channels": { "type": "multi_channels_select", "selected_channels": ["C2", "C3"] },
+                    "upload": { "type": "file_input", "files": [{ "id": "F1", "name": "a.png" }] },
+                    "notes": {
+                        "type": "rich_text_input",
+                        "rich_text_value": { "type": "rich_text", "elements": [] }
+                    },
+                    "broken": { "type": "datepicker", "selected_date": "tomorrow" }
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(state.get_value("form", "name").unwrap(), "Bolt");
+        assert!((state.get_number("form", "age").unwrap() - 4.5).abs() < f64::EPSILON);
+        assert!(state.get_number("form", "name").is_err());
+        assert_eq!(state.get_value("form", "owner").unwrap(), "U1");
+
+        let fruit = state.get_option("form", "fruit").unwrap();
+        assert_eq!(fruit.get_value(), "apple");
+        assert_eq!(json::to_value(fruit.get_text()).unwrap()["text"], "Apple");
+
+        let toppings: Vec<&str> = state
+            .get_options("form", "toppings")
+            .unwrap()
+            .iter()
+            .map(|option| option.get_value())
+            .collect();
+        assert_eq!(toppings, ["cheese", "ham"]);
+
+        assert_eq!(
+            state.get_date("form", "date").unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        assert_eq!(
+            state.get_time("form", "time").unwrap(),
+            chrono::NaiveTime::from_hms_opt(13, 37, 0).unwrap()
+        );
+        assert_eq!(
+            state.get_date_time("form", "when").unwrap().timestamp(),
+            1_700_000_000
+        );
+        assert_eq!(state.get_users("form", "users").unwrap(), ["U1", "U2"]);
+        assert_eq!(state.get_conversations("form", "convos").unwrap(), ["C1"]);
+        assert_eq!(
+            state.get_channels("form", "channels").unwrap(),
+            ["C2", "C3"]
+        );
+        assert_eq!(state.get_files("form", "upload").unwrap()[0].id(), "F1");
+        assert_eq!(
+            state.get_rich_text("form", "notes").unwrap()["type"],
+            "rich_text"
+        );
+
+        assert!(matches!(
+            state.get_date("form", "broken"),
+            Err(Error::State(_))
+        ));
+        assert!(matches!(
+            state.get_date("form", "name"),
+            Err(Error::State(_))
+        ));
+        assert!(matches!(
+            state.get_options("form", "missing"),
+            Err(Error::State(_))
+        ));
+        assert!(matches!(
+            state.get_value("other", "name"),
+            Err(Error::State(_))
+        ));
+    }
 }
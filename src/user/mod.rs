@@ -1,4 +1,7 @@
-use crate::pre::{skip_serializing_none, BoltResult, Deserialize, Request, Serialize};
+use crate::pre::{
+    collect_all, skip_serializing_none, BoltResult, Deserialize, Paginator, Serialize, WebClient,
+};
+use futures::stream::BoxStream;
 
 #[skip_serializing_none]
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -58,16 +61,24 @@ pub struct List(Vec<User>);
 pub struct Team {
     pub id: String,
     pub domain: String,
+    pub enterprise_id: Option<String>,
+    pub enterprise_name: Option<String>,
 }
 
 impl List {
-    /// Gets a complete list of users from slack
+    /// Gets a complete list of users from slack, following every page of `users.list`.
     ///
     /// # Errors
     ///
     /// An error will occur if the request fails to be sent or if slack reports any errors back.
-    pub async fn new(token: &str) -> BoltResult<Self> {
-        Request::post("users.list", token).send().await?.unpack()
+    pub async fn new(client: &WebClient) -> BoltResult<Self> {
+        Ok(Self(collect_all(Self::stream(client), None).await?))
+    }
+
+    /// Streams every user from slack, requesting the pages of `users.list` as needed.
+    #[must_use]
+    pub fn stream(client: &WebClient) -> BoxStream<'static, BoltResult<User>> {
+        Paginator::new(client, "users.list").stream()
     }
 
     /// Gets a specific user at an index
@@ -105,8 +116,9 @@ impl User {
     ///
     /// An error will occur if the request fails to be sent, or if slack reports any errors back.
     ///
-    pub async fn from_id(token: &str, id: &str) -> BoltResult<Self> {
-        Request::get(&format!("users.info?user={id}"), token)
+    pub async fn from_id(client: &WebClient, id: &str) -> BoltResult<Self> {
+        client
+            .get(&format!("users.info?user={id}"))
             .send()
             .await?
             .unpack()
@@ -118,8 +130,9 @@ impl User {
     ///
     /// An error will occur if the request fails to be sent, or if slack reports any errors back.
     ///
-    pub async fn from_email(token: &str, email: &str) -> BoltResult<Self> {
-        Request::get(&format!("users.lookupByEmail?email={email}"), token)
+    pub async fn from_email(client: &WebClient, email: &str) -> BoltResult<Self> {
+        client
+            .get(&format!("users.lookupByEmail?email={email}"))
             .send()
             .await?
             .unpack()
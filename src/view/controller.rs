@@ -1,10 +1,14 @@
-use super::{parsing, Debug, Deserialize, Serialize, View};
+use super::{parsing, skip_serializing_none, Debug, Deserialize, Serialize, View};
 
+#[skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct Controller<T: parsing::SerializeDefaultPhantomData> {
     view: View<T>,
     trigger_id: Option<String>,
     external_id: Option<String>,
+    view_id: Option<String>,
+    user_id: Option<String>,
+    hash: Option<String>,
 }
 
 impl<T: Serialize + parsing::SerializeDefaultPhantomData> Controller<T> {
@@ -15,6 +19,9 @@ impl<T: Serialize + parsing::SerializeDefaultPhantomData> Controller<T> {
             view,
             trigger_id: Some(trigger.to_string()),
             external_id: None,
+            view_id: None,
+            user_id: None,
+            hash: None,
         }
     }
 
@@ -25,6 +32,35 @@ impl<T: Serialize + parsing::SerializeDefaultPhantomData> Controller<T> {
             external_id: view.external_id.clone(),
             view,
             trigger_id: None,
+            view_id: None,
+            user_id: None,
+            hash: None,
+        }
+    }
+
+    /// Creates a new [`Controller`] to update a view by its id, only if the hash matches the current view
+    #[must_use]
+    pub fn update_by_id(view_id: &str, hash: Option<&str>, view: View<T>) -> Self {
+        Self {
+            view,
+            trigger_id: None,
+            external_id: None,
+            view_id: Some(view_id.to_string()),
+            user_id: None,
+            hash: hash.map(ToString::to_string),
+        }
+    }
+
+    /// Creates a new [`Controller`] to publish a view to a user
+    #[must_use]
+    pub fn publish(user_id: &str, hash: Option<&str>, view: View<T>) -> Self {
+        Self {
+            view,
+            trigger_id: None,
+            external_id: None,
+            view_id: None,
+            user_id: Some(user_id.to_string()),
+            hash: hash.map(ToString::to_string),
         }
     }
 }
@@ -1,15 +1,22 @@
+use crate::core::signatures_match;
 use crate::pre::{
     block,
     comp::{Plain, Text},
-    parsing, skip_serializing_none, state, BoltResult, Build, Deserialize, Error, HomeTab, Modal,
-    ModalResponse, Request, Serialize,
+    json, parsing, skip_serializing_none, state, BoltResult, Build, Deserialize, Error, HomeTab,
+    Modal, ModalResponse, Serialize, WebClient,
 };
+use hmac_sha256::HMAC;
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 
+/// The maximum amount of characters in the private-metadata of a view.
+const PRIVATE_METADATA_LIMIT: usize = 3000;
+
 mod controller;
+mod response;
 
 pub use controller::Controller;
+pub use response::ViewSubmissionResponse;
 
 /// Convert any type into a view
 #[allow(clippy::module_name_repetitions)]
@@ -47,6 +54,58 @@ pub struct View<T: parsing::SerializeDefaultPhantomData = ModalResponse> {
 
     // For usage in submission payload:
     state: Option<state::State>,
+
+    // Set by slack, and never sent back:
+    #[serde(skip_serializing)]
+    id: Option<String>,
+    #[serde(skip_serializing)]
+    hash: Option<String>,
+    #[serde(skip_serializing)]
+    root_view_id: Option<String>,
+    #[serde(skip_serializing)]
+    previous_view_id: Option<String>,
+}
+
+/// A handle to a view opened, pushed, updated or published in slack.
+///
+/// The hash changes with every update of the view, and is used to detect concurrent updates.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ViewHandle {
+    /// The id of the view.
+    pub id: String,
+
+    /// The hash of the current version of the view.
+    #[serde(default)]
+    pub hash: String,
+
+    /// The id of the first view in the modal stack.
+    pub root_view_id: Option<String>,
+
+    /// The id of the view below this view in the modal stack.
+    pub previous_view_id: Option<String>,
+
+    /// The external-id of the view.
+    pub external_id: Option<String>,
+}
+
+impl ViewHandle {
+    /// Replaces the view, only if it hasn't been updated since this handle was returned.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the request fails to be sent, or if slack reports any errors back.
+    /// A concurrent update of the view is reported as [`SlackApiError::HashConflict`](crate::SlackApiError::HashConflict).
+    ///
+    pub async fn update<
+        T: DeserializeOwned + Serialize + parsing::SerializeDefaultPhantomData + Debug + Send + Sync,
+    >(
+        &self,
+        view: View<T>,
+        client: &WebClient,
+    ) -> BoltResult<Self> {
+        view.update_by_id(&self.id, Some(&self.hash), client).await
+    }
 }
 impl View {
     /// Creates a new [`View`] for a home-tab
@@ -95,35 +154,167 @@ impl<
         self
     }
 
+    /// Sets the private-metadata of the view to typed metadata, encoded as compact JSON.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the metadata fails to serialize, or exceeds 3000 characters.
+    ///
+    pub fn metadata<M: Serialize>(self, metadata: &M) -> BoltResult<Self> {
+        self.encode_metadata(metadata, None)
+    }
+
+    /// Sets the private-metadata of the view to typed metadata, signed with the signing-secret of the app
+    /// so it can't be tampered with.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the metadata fails to serialize, or exceeds 3000 characters with the signature.
+    ///
+    pub fn signed_metadata<M: Serialize>(
+        self,
+        metadata: &M,
+        signing_secret: &str,
+    ) -> BoltResult<Self> {
+        self.encode_metadata(metadata, Some(signing_secret))
+    }
+
+    /// Sets the private-metadata of the view to typed metadata, encoded as compact JSON.
+    /// If a signing-secret is supplied, the metadata is prefixed with its signature.
+    fn encode_metadata<M: Serialize>(
+        mut self,
+        metadata: &M,
+        signing_secret: Option<&str>,
+    ) -> BoltResult<Self> {
+        let mut encoded = json::to_string(metadata)
+            .map_err(|error| Error::View(format!("Couldn't serialize metadata: {error}")))?;
+
+        if let Some(secret) = signing_secret {
+            encoded = format!("{}.{encoded}", sign(&encoded, secret));
+        }
+
+        let length = encoded.chars().count();
+        if length > PRIVATE_METADATA_LIMIT {
+            return Err(Error::View(format!(
+                "Private-metadata is {length} characters, exceeding the limit of {PRIVATE_METADATA_LIMIT}."
+            )));
+        }
+
+        self.private_metadata = Some(encoded);
+        Ok(self)
+    }
+
+    /// Returns a handle to the view, if it was returned from slack.
+    #[must_use]
+    pub fn handle(&self) -> Option<ViewHandle> {
+        Some(ViewHandle {
+            id: self.id.clone()?,
+            hash: self.hash.clone().unwrap_or_default(),
+            root_view_id: self.root_view_id.clone(),
+            previous_view_id: self.previous_view_id.clone(),
+            external_id: self.external_id.clone(),
+        })
+    }
+
     /// Opens a new view for the supplied trigger-id
     ///
     /// # Errors
     ///
     /// An error will occur if the request fails to be sent, or if slack reports any errors back.
     ///
-    pub async fn open(self, trigger_id: &str, token: &str) -> BoltResult<Self> {
-        Request::post("views.open", token)
+    pub async fn open(self, trigger_id: &str, client: &WebClient) -> BoltResult<ViewHandle> {
+        client
+            .post("views.open")
             .json(&Controller::trigger(trigger_id, self))
             .send()
             .await?
             .unpack()
     }
 
-    /// Updates the current view
+    /// Pushes the view onto the stack of an open modal, for the supplied trigger-id.
+    /// A modal can have at most 3 views in its stack.
     ///
     /// # Errors
     ///
     /// An error will occur if the request fails to be sent, or if slack reports any errors back.
     ///
-    pub async fn update(self, token: &str) -> BoltResult<Self> {
-        Request::post("views.update", token)
+    pub async fn push(self, trigger_id: &str, client: &WebClient) -> BoltResult<ViewHandle> {
+        client
+            .post("views.push")
+            .json(&Controller::trigger(trigger_id, self))
+            .send()
+            .await?
+            .unpack()
+    }
+
+    /// Updates the view with the same external-id
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn update(self, client: &WebClient) -> BoltResult<ViewHandle> {
+        client
+            .post("views.update")
             .json(&Controller::update(self))
             .send()
             .await?
             .unpack()
     }
+
+    /// Replaces the view with the supplied view-id.
+    /// If a hash is supplied, the update fails with `hash_conflict` if the view has been updated since.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn update_by_id(
+        self,
+        view_id: &str,
+        hash: Option<&str>,
+        client: &WebClient,
+    ) -> BoltResult<ViewHandle> {
+        client
+            .post("views.update")
+            .json(&Controller::update_by_id(view_id, hash, self))
+            .send()
+            .await?
+            .unpack()
+    }
+}
+impl View<HomeTab> {
+    /// Publishes the view as the home-tab of a user.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn publish(self, user_id: &str, client: &WebClient) -> BoltResult<ViewHandle> {
+        self.publish_with_hash(user_id, None, client).await
+    }
+
+    /// Publishes the view as the home-tab of a user, only if the hash matches the current home-tab.
+    /// This prevents overwriting a home-tab that was published concurrently, which fails with `hash_conflict`.
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the request fails to be sent, or if slack reports any errors back.
+    ///
+    pub async fn publish_with_hash(
+        self,
+        user_id: &str,
+        hash: Option<&str>,
+        client: &WebClient,
+    ) -> BoltResult<ViewHandle> {
+        client
+            .post("views.publish")
+            .json(&Controller::publish(user_id, hash, self))
+            .send()
+            .await?
+            .unpack()
+    }
 }
-impl View<HomeTab> {}
 impl View<Modal> {
     /// Sets the text for the "close" button
     #[must_use]
@@ -176,6 +367,54 @@ impl View<ModalResponse> {
         self.private_metadata.as_ref()
     }
 
+    /// Gets the typed private-metadata from the view, as set by [`View::metadata`].
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the view has no private-metadata, or if it fails to deserialize.
+    ///
+    pub fn get_metadata<M: DeserializeOwned>(&self) -> BoltResult<M> {
+        decode_metadata(self.private_metadata.as_deref())
+    }
+
+    /// Gets the typed private-metadata from the view, verifying the signature
+    /// set by [`View::signed_metadata`].
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the view has no private-metadata, if the signature doesn't match,
+    /// or if it fails to deserialize.
+    ///
+    pub fn verified_metadata<M: DeserializeOwned>(&self, signing_secret: &str) -> BoltResult<M> {
+        let Some((signature, metadata)) = self
+            .private_metadata
+            .as_deref()
+            .and_then(|metadata| metadata.split_once('.'))
+        else {
+            return Err(Error::View("Private-metadata isn't signed.".to_string()));
+        };
+
+        if !signatures_match(signature, &sign(metadata, signing_secret)) {
+            return Err(Error::View(
+                "Signature of private-metadata didn't match!".to_string(),
+            ));
+        }
+
+        decode_metadata(Some(metadata))
+    }
+
+    /// Gets the state of the view, for the typed getters of [`state::State`].
+    ///
+    /// # Errors
+    ///
+    /// An error will occur if the view has no state.
+    ///
+    pub fn get_state(&self) -> BoltResult<&state::State> {
+        self.state
+            .as_ref()
+            .ok_or_else(|| Error::View("Tried to get state from view without state.".to_string()))
+    }
+
     /// Gets a state-value from the view
     ///
     /// # Errors
@@ -192,3 +431,21 @@ impl View<ModalResponse> {
         ))
     }
 }
+
+/// Signs private-metadata with a signing-secret.
+fn sign(metadata: &str, signing_secret: &str) -> String {
+    hex::encode(HMAC::mac(
+        format!("private_metadata:{metadata}"),
+        signing_secret,
+    ))
+}
+
+/// Deserializes private-metadata encoded as JSON.
+fn decode_metadata<M: DeserializeOwned>(metadata: Option<&str>) -> BoltResult<M> {
+    let Some(metadata) = metadata else {
+        return Err(Error::View("View has no private-metadata.".to_string()));
+    };
+
+    json::from_str(metadata)
+        .map_err(|error| Error::View(format!("Couldn't deserialize private-metadata: {error}")))
+}
@@ -0,0 +1,54 @@
+use super::{Deserialize, Modal, Serialize, View};
+use std::collections::HashMap;
+
+/// A response to a `view_submission` interaction.
+///
+/// This is sent back to slack as the reply to the submission, and can be used to
+/// show validation-errors, update the modal, push a new view, or close every view in the stack.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "response_action", rename_all = "snake_case")]
+pub enum ViewSubmissionResponse {
+    /// Shows errors on the input-blocks of the modal, mapped by block-id.
+    Errors { errors: HashMap<String, String> },
+
+    /// Updates the currently open modal.
+    Update { view: View<Modal> },
+
+    /// Pushes a new view onto the modal stack.
+    Push { view: View<Modal> },
+
+    /// Closes every view in the modal stack.
+    Clear,
+}
+
+impl ViewSubmissionResponse {
+    /// Creates a response showing errors on the input-blocks with the given block-ids.
+    #[must_use]
+    pub fn errors<K: ToString, V: ToString>(errors: impl IntoIterator<Item = (K, V)>) -> Self {
+        Self::Errors {
+            errors: errors
+                .into_iter()
+                .map(|(block_id, error)| (block_id.to_string(), error.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Creates a response updating the currently open modal.
+    #[must_use]
+    pub const fn update(view: View<Modal>) -> Self {
+        Self::Update { view }
+    }
+
+    /// Creates a response pushing a new view onto the modal stack.
+    #[must_use]
+    pub const fn push(view: View<Modal>) -> Self {
+        Self::Push { view }
+    }
+
+    /// Creates a response closing every view in the modal stack.
+    #[must_use]
+    pub const fn clear() -> Self {
+        Self::Clear
+    }
+}